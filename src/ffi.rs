@@ -0,0 +1,123 @@
+//! C bindings for the osu!standard 2022 calculator.
+//!
+//! Both functions parse the content of a `.osu` file from the given buffer
+//! and return `NaN` if the buffer is null or the map could not be parsed.
+//!
+//! `cargo build --release --features ffi` produces a shared library to link
+//! against. For a static library, build with
+//! `cargo rustc --release --features ffi --crate-type staticlib` instead.
+//!
+//! ```c
+//! #include <stddef.h>
+//! #include <stdint.h>
+//!
+//! double rosu_osu2022_pp(const uint8_t *map_ptr, size_t map_len, uint32_t mods, double acc);
+//! double rosu_osu2022_stars(const uint8_t *map_ptr, size_t map_len, uint32_t mods);
+//! ```
+
+use std::slice;
+
+use rosu_pp::Beatmap;
+
+use crate::osu_2022::{OsuPP, OsuStars};
+
+/// Calculate the pp of a play with the given mods and accuracy between
+/// `0.0` and `100.0`.
+///
+/// # Safety
+///
+/// `map_ptr` must either be null or point to `map_len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn rosu_osu2022_pp(
+    map_ptr: *const u8,
+    map_len: usize,
+    mods: u32,
+    acc: f64,
+) -> f64 {
+    let Some(map) = parse_map(map_ptr, map_len) else {
+        return f64::NAN;
+    };
+
+    OsuPP::new(&map).mods(mods).accuracy(acc).calculate().pp
+}
+
+/// Calculate the star rating with the given mods.
+///
+/// # Safety
+///
+/// `map_ptr` must either be null or point to `map_len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn rosu_osu2022_stars(map_ptr: *const u8, map_len: usize, mods: u32) -> f64 {
+    let Some(map) = parse_map(map_ptr, map_len) else {
+        return f64::NAN;
+    };
+
+    OsuStars::new().mods(mods).calculate(&map).stars
+}
+
+unsafe fn parse_map(map_ptr: *const u8, map_len: usize) -> Option<Beatmap> {
+    if map_ptr.is_null() || map_len == 0 {
+        return None;
+    }
+
+    let bytes = slice::from_raw_parts(map_ptr, map_len);
+
+    Beatmap::from_bytes(bytes).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ptr;
+
+    use super::*;
+
+    const MAP: &[u8] = b"osu file format v14
+
+[General]
+Mode: 0
+
+[Difficulty]
+HPDrainRate:5
+CircleSize:4
+OverallDifficulty:8
+ApproachRate:9
+SliderMultiplier:1.4
+SliderTickRate:1
+
+[TimingPoints]
+0,300,4,2,0,100,1,0
+
+[HitObjects]
+64,64,1000,1,0,0:0:0:0:
+448,64,1150,1,0,0:0:0:0:
+448,320,1300,1,0,0:0:0:0:
+64,320,1450,1,0,0:0:0:0:
+256,192,1600,1,0,0:0:0:0:
+64,64,1750,1,0,0:0:0:0:
+";
+
+    #[test]
+    fn round_trip() {
+        let map = Beatmap::from_bytes(MAP).unwrap();
+
+        let pp = unsafe { rosu_osu2022_pp(MAP.as_ptr(), MAP.len(), 8, 98.0) };
+        let expected = OsuPP::new(&map).mods(8).accuracy(98.0).calculate().pp;
+        assert!(pp > 0.0);
+        assert_eq!(pp, expected);
+
+        let stars = unsafe { rosu_osu2022_stars(MAP.as_ptr(), MAP.len(), 8) };
+        let expected = OsuStars::new().mods(8).calculate(&map).stars;
+        assert!(stars > 0.0);
+        assert_eq!(stars, expected);
+    }
+
+    #[test]
+    fn invalid_buffer() {
+        unsafe {
+            assert!(rosu_osu2022_pp(ptr::null(), MAP.len(), 0, 100.0).is_nan());
+            assert!(rosu_osu2022_stars(ptr::null(), MAP.len(), 0).is_nan());
+            assert!(rosu_osu2022_pp(MAP.as_ptr(), 0, 0, 100.0).is_nan());
+            assert!(rosu_osu2022_stars(MAP.as_ptr(), 0, 0).is_nan());
+        }
+    }
+}
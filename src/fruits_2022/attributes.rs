@@ -1,5 +1,8 @@
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
 /// The result of a difficulty calculation on an osu!catch map.
 #[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CatchDifficultyAttributes {
     /// The final star rating
     pub stars: f64,
@@ -11,6 +14,8 @@ pub struct CatchDifficultyAttributes {
     pub n_droplets: u32,
     /// The amount of tiny droplets.
     pub n_tiny_droplets: u32,
+    /// The amount of objects that require a hyper dash to be caught.
+    pub n_hyper_dashes: u32,
     /// Whether the [`Beatmap`] was a convert i.e. an osu!standard map.
     ///
     /// [`Beatmap`]: crate::model::beatmap::Beatmap
@@ -23,6 +28,11 @@ impl CatchDifficultyAttributes {
         self.n_fruits + self.n_droplets
     }
 
+    /// Return the amount of objects that require a hyper dash to be caught.
+    pub const fn n_hyper_dashes(&self) -> u32 {
+        self.n_hyper_dashes
+    }
+
     /// Whether the [`Beatmap`] was a convert i.e. an osu!standard map.
     ///
     /// [`Beatmap`]: crate::model::beatmap::Beatmap
@@ -39,11 +49,15 @@ impl CatchDifficultyAttributes {
 
 /// The result of a performance calculation on an osu!catch map.
 #[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CatchPerformanceAttributes {
     /// The difficulty attributes that were used for the performance calculation
     pub difficulty: CatchDifficultyAttributes,
     /// The final performance points.
     pub pp: f64,
+    /// The amount of hitresults of the score, i.e. the sum of fruits,
+    /// droplets, tiny droplets, tiny droplet misses, and misses.
+    pub total_hits: u32,
 }
 
 impl CatchPerformanceAttributes {
@@ -62,6 +76,14 @@ impl CatchPerformanceAttributes {
         self.difficulty.max_combo()
     }
 
+    /// Return the amount of hitresults of the score, i.e. the sum of fruits,
+    /// droplets, tiny droplets, tiny droplet misses, and misses.
+    ///
+    /// For partial plays, this only covers the passed objects.
+    pub const fn total_hits(&self) -> u32 {
+        self.total_hits
+    }
+
     /// Whether the [`Beatmap`] was a convert i.e. an osu!standard map.
     ///
     /// [`Beatmap`]: crate::model::beatmap::Beatmap
@@ -70,6 +92,14 @@ impl CatchPerformanceAttributes {
     }
 }
 
+/// Concise summary of the pp, rounded to one decimal,
+/// e.g. `pp=412.3`.
+impl Display for CatchPerformanceAttributes {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "pp={:.1}", self.pp)
+    }
+}
+
 impl From<CatchPerformanceAttributes> for CatchDifficultyAttributes {
     fn from(attributes: CatchPerformanceAttributes) -> Self {
         attributes.difficulty
@@ -83,40 +113,110 @@ pub struct ObjectCount {
     tiny_droplets: u32,
 }
 
-pub struct ObjectCountBuilder {
-    count: ObjectCount,
-    take: usize,
+pub enum ObjectCountBuilder {
+    Regular {
+        count: ObjectCount,
+        take: usize,
+    },
+    /// Stores the accumulated count after each fruit or droplet.
+    Gradual {
+        count: ObjectCount,
+        all: Vec<ObjectCount>,
+    },
 }
 
 impl ObjectCountBuilder {
     pub fn new(take: usize) -> Self {
-        Self {
+        Self::Regular {
             count: ObjectCount::default(),
             take,
         }
     }
 
+    pub fn new_gradual() -> Self {
+        Self::Gradual {
+            count: ObjectCount::default(),
+            all: Vec::with_capacity(512),
+        }
+    }
+
     pub fn into_regular(self) -> ObjectCount {
-        self.count
+        match self {
+            Self::Regular { count, .. } | Self::Gradual { count, .. } => count,
+        }
+    }
+
+    pub fn into_gradual(self) -> Box<[ObjectCount]> {
+        match self {
+            Self::Regular { .. } => Box::default(),
+            Self::Gradual { all, .. } => all.into_boxed_slice(),
+        }
     }
 
     pub fn record_fruit(&mut self) {
-        if self.take > 0 {
-            self.take -= 1;
-            self.count.fruits += 1;
+        match self {
+            Self::Regular { count, take } => {
+                if *take > 0 {
+                    *take -= 1;
+                    count.fruits += 1;
+                }
+            }
+            Self::Gradual { count, all } => {
+                count.fruits += 1;
+                all.push(count.clone());
+            }
         }
     }
 
     pub fn record_droplet(&mut self) {
-        if self.take > 0 {
-            self.take -= 1;
-            self.count.droplets += 1;
+        match self {
+            Self::Regular { count, take } => {
+                if *take > 0 {
+                    *take -= 1;
+                    count.droplets += 1;
+                }
+            }
+            Self::Gradual { count, all } => {
+                count.droplets += 1;
+                all.push(count.clone());
+            }
         }
     }
 
     pub fn record_tiny_droplets(&mut self, n: u32) {
-        if self.take > 0 {
-            self.count.tiny_droplets += n;
+        match self {
+            Self::Regular { count, take } => {
+                if *take > 0 {
+                    count.tiny_droplets += n;
+                }
+            }
+            Self::Gradual { count, .. } => count.tiny_droplets += n,
         }
     }
 }
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_round_trip() {
+        let attrs = CatchPerformanceAttributes {
+            difficulty: CatchDifficultyAttributes {
+                stars: 5.5,
+                ar: 9.25,
+                n_fruits: 600,
+                n_droplets: 150,
+                n_tiny_droplets: 900,
+                n_hyper_dashes: 12,
+                is_convert: false,
+            },
+            pp: 310.75,
+            total_hits: 1650,
+        };
+
+        let json = serde_json::to_string(&attrs).unwrap();
+        let deserialized: CatchPerformanceAttributes = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, attrs);
+    }
+}
@@ -0,0 +1,285 @@
+use std::cmp;
+
+use rosu_pp::{catch::CatchScoreState, model::mode::GameMode, Beatmap};
+
+use crate::util::{
+    convert::{convert_ref, ConvertError},
+    skills::Skill,
+};
+
+use super::{
+    attributes::{ObjectCount, ObjectCountBuilder},
+    convert::convert_objects,
+    difficulty_object::CatchDifficultyObject,
+    movement::Movement,
+    CatchDifficultyAttributes, CatchDifficultySetup, CatchPerformanceAttributes, CatchStars,
+    DifficultyValues, FruitsPP,
+};
+
+/// Gradually calculate the difficulty attributes of an osu!catch map.
+///
+/// Note that this struct implements [`Iterator`].
+/// On every call of [`Iterator::next`], the map's next fruit or droplet will
+/// be processed and the [`CatchDifficultyAttributes`] will be updated and
+/// returned.
+///
+/// If you want to calculate performance attributes, use
+/// [`CatchGradualPerformance`] instead.
+pub struct CatchGradualDifficulty {
+    idx: usize,
+    difficulty: CatchStars,
+    attrs: CatchDifficultyAttributes,
+    /// The accumulated object count after each palpable object.
+    count: Box<[ObjectCount]>,
+    /// Whether each palpable object requires a hyper dash.
+    hyper_dash: Box<[bool]>,
+    diff_objects: Box<[CatchDifficultyObject]>,
+    movement: Movement,
+}
+
+impl CatchGradualDifficulty {
+    /// Create a [`CatchGradualDifficulty`] for a map.
+    ///
+    /// If the map cannot be converted to osu!catch, the iterator will not
+    /// yield any attributes. Use [`checked_new`](Self::checked_new) to handle
+    /// that case explicitly.
+    pub fn new(difficulty: CatchStars, map: &Beatmap) -> Self {
+        Self::checked_new(difficulty, map).unwrap_or_else(|_| Self {
+            idx: 0,
+            difficulty,
+            attrs: CatchDifficultyAttributes::default(),
+            count: Box::default(),
+            hyper_dash: Box::default(),
+            diff_objects: Box::default(),
+            movement: Movement::new(1.0),
+        })
+    }
+
+    /// Create a [`CatchGradualDifficulty`] for a map.
+    ///
+    /// Returns an error if the map cannot be converted to osu!catch.
+    pub fn checked_new(difficulty: CatchStars, map: &Beatmap) -> Result<Self, ConvertError> {
+        let map = convert_ref(map, GameMode::Catch, &difficulty.get_mods().into())?;
+        let map = map.as_ref();
+
+        let clock_rate = difficulty.get_clock_rate();
+
+        let CatchDifficultySetup { map_attrs, attrs } = CatchDifficultySetup::new(&difficulty, map);
+
+        let hr_offsets = difficulty.get_mods().hr();
+        let mut count = ObjectCountBuilder::new_gradual();
+
+        let palpable_objects = convert_objects(map, &mut count, hr_offsets, map_attrs.cs as f32);
+
+        let hyper_dash = palpable_objects.iter().map(|h| h.hyper_dash).collect();
+
+        let diff_objects = DifficultyValues::create_difficulty_objects(
+            &map_attrs,
+            clock_rate,
+            palpable_objects.iter(),
+        );
+
+        Ok(Self {
+            idx: 0,
+            difficulty,
+            attrs,
+            count: count.into_gradual(),
+            hyper_dash,
+            diff_objects,
+            movement: Movement::new(clock_rate),
+        })
+    }
+
+    /// Process the next object without evaluating the difficulty.
+    ///
+    /// Returns `false` if there are no objects left.
+    fn process_next(&mut self) -> bool {
+        let Some(count) = self.count.get(self.idx) else {
+            return false;
+        };
+
+        // The first palpable object has no difficulty object
+        if let Some(curr) = self
+            .idx
+            .checked_sub(1)
+            .and_then(|i| self.diff_objects.get(i))
+        {
+            Skill::new(&mut self.movement, &self.diff_objects).process(curr);
+        }
+
+        self.attrs.set_object_count(count);
+
+        if self.hyper_dash.get(self.idx).copied().unwrap_or(false) {
+            self.attrs.n_hyper_dashes += 1;
+        }
+
+        self.idx += 1;
+
+        true
+    }
+}
+
+impl Iterator for CatchGradualDifficulty {
+    type Item = CatchDifficultyAttributes;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.process_next() {
+            return None;
+        }
+
+        let mut attrs = self.attrs.clone();
+        DifficultyValues::eval(&mut attrs, self.movement.as_difficulty_value());
+
+        Some(attrs)
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        for _ in 0..cmp::min(n, self.len()) {
+            self.process_next();
+        }
+
+        self.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for CatchGradualDifficulty {
+    fn len(&self) -> usize {
+        self.count.len() - self.idx
+    }
+}
+
+/// Gradually calculate the performance attributes of an osu!catch map.
+///
+/// After each hit object you can call [`next`] and it will return the
+/// resulting current [`CatchPerformanceAttributes`]. To process multiple
+/// objects at once, use [`nth`] instead.
+///
+/// Both methods require a [`CatchScoreState`] that contains the current
+/// hitresults as well as the maximum combo so far.
+///
+/// If you only want to calculate difficulty attributes use
+/// [`CatchGradualDifficulty`] instead.
+///
+/// [`next`]: CatchGradualPerformance::next
+/// [`nth`]: CatchGradualPerformance::nth
+pub struct CatchGradualPerformance<'map> {
+    map: &'map Beatmap,
+    difficulty: CatchGradualDifficulty,
+}
+
+impl<'map> CatchGradualPerformance<'map> {
+    /// Create a new gradual performance calculator for osu!catch maps.
+    pub fn new(difficulty: CatchStars, map: &'map Beatmap) -> Self {
+        let difficulty = CatchGradualDifficulty::new(difficulty, map);
+
+        Self { map, difficulty }
+    }
+
+    /// Process the next fruit or droplet and calculate the performance
+    /// attributes for the resulting score state.
+    pub fn next(&mut self, state: CatchScoreState) -> Option<CatchPerformanceAttributes> {
+        self.nth(state, 0)
+    }
+
+    /// Process all remaining fruits and droplets and calculate the final
+    /// performance attributes.
+    pub fn last(&mut self, state: CatchScoreState) -> Option<CatchPerformanceAttributes> {
+        self.nth(state, usize::MAX)
+    }
+
+    /// Process everything up to the next `n`th fruit or droplet and calculate
+    /// the performance attributes for the resulting score state.
+    ///
+    /// Note that the count is zero-indexed, so `n=0` will process 1 object,
+    /// `n=1` will process 2, and so on.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn nth(&mut self, state: CatchScoreState, n: usize) -> Option<CatchPerformanceAttributes> {
+        let n = cmp::min(n, self.difficulty.len().saturating_sub(1));
+        let attrs = self.difficulty.nth(n)?;
+
+        let performance = FruitsPP::new(self.map)
+            .difficulty(self.difficulty.difficulty.clone())
+            .attributes(attrs)
+            .state(state)
+            .calculate();
+
+        Some(performance)
+    }
+
+    /// Returns the amount of remaining objects.
+    pub fn len(&self) -> usize {
+        self.difficulty.len()
+    }
+
+    /// Whether all objects have been processed.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn map() -> Beatmap {
+        let hit_objects: String = (0..60)
+            .map(|i| format!("{},192,{},1,0,0:0:0:0:\n", (i * 97) % 512, 500 + i * 200))
+            .collect();
+
+        let content = format!(
+            "osu file format v14
+
+[General]
+Mode: 2
+
+[Difficulty]
+HPDrainRate:5
+CircleSize:4
+OverallDifficulty:8
+ApproachRate:9
+SliderMultiplier:1.4
+SliderTickRate:1
+
+[TimingPoints]
+0,300,4,2,0,100,1,0
+
+[HitObjects]
+{hit_objects}"
+        );
+
+        Beatmap::from_bytes(content.as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn last_matches_regular_calculation() {
+        let map = map();
+        let difficulty = CatchStars::new().mods(8);
+        let attrs = difficulty.calculate(&map);
+
+        let state = CatchScoreState {
+            max_combo: attrs.max_combo(),
+            fruits: attrs.n_fruits,
+            droplets: attrs.n_droplets,
+            tiny_droplets: attrs.n_tiny_droplets,
+            tiny_droplet_misses: 0,
+            misses: 0,
+        };
+
+        let mut gradual = CatchGradualPerformance::new(difficulty.clone(), &map);
+        let last = gradual.last(state.clone()).unwrap();
+
+        let expected = FruitsPP::new(&map)
+            .difficulty(difficulty)
+            .state(state)
+            .calculate();
+
+        assert_eq!(last, expected);
+        assert!(gradual.is_empty());
+    }
+}
@@ -10,14 +10,19 @@ use convert::convert_objects;
 use difficulty_object::CatchDifficultyObject;
 use movement::Movement;
 use rosu_pp::{
-    model::{beatmap::BeatmapAttributes, mode::GameMode},
+    model::{beatmap::BeatmapAttributes, mode::GameMode, mods::GameMods},
     Beatmap,
 };
 
-use crate::util::{mods::Mods, skills::Skill};
+use crate::util::{
+    convert::{convert_ref, ConvertError},
+    mods::{lazer_clock_rate, legacy_bits, Mods},
+    skills::Skill,
+};
 
 pub use self::{
     attributes::{CatchDifficultyAttributes, CatchPerformanceAttributes},
+    gradual::{CatchGradualDifficulty, CatchGradualPerformance},
     pp::*,
 };
 
@@ -26,12 +31,15 @@ mod catch_object;
 mod catcher;
 mod convert;
 mod difficulty_object;
+mod gradual;
 mod movement;
 mod pp;
 
 const PLAYFIELD_WIDTH: f32 = 512.0;
 
-const STAR_SCALING_FACTOR: f64 = 0.153;
+/// Scales the square root of the movement difficulty value into the star
+/// rating; raised from `0.145` of ppv1.
+pub const STAR_SCALING_FACTOR: f64 = 0.153;
 
 /// Difficulty calculator on maps of any mode.
 ///
@@ -60,6 +68,10 @@ pub struct CatchStars {
     /// This allows for an optimization to reduce the struct size by storing its
     /// bits as a [`NonZeroU32`].
     clock_rate: Option<NonZeroU32>,
+    /// Clock rate of lazer mods, including custom speed changes.
+    ///
+    /// Only used if no clock rate was specified explicitly.
+    mods_clock_rate: Option<f32>,
 }
 
 impl CatchStars {
@@ -69,14 +81,33 @@ impl CatchStars {
             mods: 0,
             passed_objects: None,
             clock_rate: None,
+            mods_clock_rate: None,
         }
     }
 
     /// Specify mods.
     ///
+    /// Accepted types are
+    /// - `u32`
+    /// - [`rosu_mods::GameModsLegacy`]
+    /// - [`rosu_mods::GameMods`]
+    /// - [`rosu_mods::GameModsIntermode`]
+    /// - [`&rosu_mods::GameModsIntermode`](rosu_mods::GameModsIntermode)
+    ///
+    /// Mods are converted to their legacy bit values so lazer mods without
+    /// a legacy counterpart are ignored. The custom speed change of lazer
+    /// rate changing mods is kept and used unless a clock rate is specified
+    /// through [`clock_rate`](Self::clock_rate).
+    ///
     /// See <https://github.com/ppy/osu-api/wiki#mods>
-    pub const fn mods(self, mods: u32) -> Self {
-        Self { mods, ..self }
+    pub fn mods(self, mods: impl Into<GameMods>) -> Self {
+        let mods = mods.into();
+
+        Self {
+            mods: legacy_bits(&mods),
+            mods_clock_rate: lazer_clock_rate(&mods).map(|clock_rate| clock_rate as f32),
+            ..self
+        }
     }
 
     /// Amount of passed objects for partial plays, e.g. a fail.
@@ -91,10 +122,24 @@ impl CatchStars {
     /// If none is specified, it will take the clock rate based on the mods
     /// i.e. 1.5 for DT, 0.75 for HT and 1.0 otherwise.
     ///
+    /// A specified clock rate always takes precedence over the mods, e.g.
+    /// DT with a clock rate of 0.9 uses 0.9. Use
+    /// [`reset_clock_rate`](Self::reset_clock_rate) to fall back to the mods.
+    ///
     /// | Minimum | Maximum |
     /// | :-----: | :-----: |
     /// | 0.01    | 100     |
+    ///
+    /// A `NaN` or infinite value is ignored, i.e. the clock rate based on the
+    /// mods is used.
     pub fn clock_rate(self, clock_rate: f64) -> Self {
+        if !clock_rate.is_finite() {
+            return Self {
+                clock_rate: None,
+                ..self
+            };
+        }
+
         let clock_rate = (clock_rate as f32).clamp(0.01, 100.0).to_bits();
 
         // SAFETY: The minimum value is 0.01 so its bits can never be fully
@@ -107,11 +152,28 @@ impl CatchStars {
         }
     }
 
+    /// Remove a previously specified clock rate so that the clock rate based
+    /// on the mods is used again.
+    pub const fn reset_clock_rate(mut self) -> Self {
+        self.clock_rate = None;
+
+        self
+    }
+
     /// Perform the difficulty calculation.
+    ///
+    /// If the map cannot be converted to the required mode, default
+    /// attributes are returned. Use [`try_calculate`](Self::try_calculate)
+    /// to handle that case explicitly.
     pub fn calculate(&self, map: &Beatmap) -> CatchDifficultyAttributes {
-        let Ok(map) = map.convert_ref(GameMode::Catch, &self.mods.into()) else {
-            return Default::default();
-        };
+        self.try_calculate(map).unwrap_or_default()
+    }
+
+    /// Perform the difficulty calculation.
+    ///
+    /// Returns an error if the map cannot be converted to the required mode.
+    pub fn try_calculate(&self, map: &Beatmap) -> Result<CatchDifficultyAttributes, ConvertError> {
+        let map = convert_ref(map, GameMode::Catch, &self.mods.into())?;
 
         let map = map.as_ref();
 
@@ -122,7 +184,73 @@ impl CatchStars {
 
         DifficultyValues::eval(&mut attrs, movement.difficulty_value());
 
-        attrs
+        Ok(attrs)
+    }
+
+    /// Perform the difficulty calculation but instead of evaluating the
+    /// movement strains, return them as is.
+    ///
+    /// Each strain covers a section of 750ms, adjusted by the clock rate.
+    pub fn movement_strains(&self, map: &Beatmap) -> Vec<f64> {
+        let Ok(map) = convert_ref(map, GameMode::Catch, &self.mods.into()) else {
+            return Vec::new();
+        };
+
+        DifficultyValues::calculate(self, map.as_ref())
+            .movement
+            .get_curr_strain_peaks()
+            .into_vec()
+    }
+
+    /// Perform the difficulty calculation but return the movement's difficulty
+    /// value before it is turned into the star rating.
+    ///
+    /// The star rating is the square root of this value multiplied by
+    /// [`STAR_SCALING_FACTOR`].
+    pub fn movement_difficulty_value(&self, map: &Beatmap) -> f64 {
+        let Ok(map) = convert_ref(map, GameMode::Catch, &self.mods.into()) else {
+            return 0.0;
+        };
+
+        DifficultyValues::calculate(self, map.as_ref())
+            .movement
+            .difficulty_value()
+    }
+
+    /// Convert the map into the objects that are processed during the
+    /// difficulty calculation without actually calculating the difficulty.
+    ///
+    /// Each object consists of its x-position, including HR offsets, and its
+    /// start time. The objects are ordered by time and consist of fruits and
+    /// droplets; tiny droplets are not included.
+    pub fn objects(&self, map: &Beatmap) -> Vec<(f32, f64)> {
+        let Ok(map) = convert_ref(map, GameMode::Catch, &self.mods.into()) else {
+            return Vec::new();
+        };
+
+        let map = map.as_ref();
+        let take = self.get_passed_objects();
+        let cs = map.attributes().mods(self.get_mods()).build().cs;
+        let mut count = ObjectCountBuilder::new(take);
+
+        convert_objects(map, &mut count, self.get_mods().hr(), cs as f32)
+            .iter()
+            .take(take)
+            .map(|h| (h.effective_x(), h.start_time))
+            .collect()
+    }
+
+    /// The mods' bit values that are used in the calculation.
+    pub const fn effective_mods(&self) -> u32 {
+        self.mods
+    }
+
+    /// The clock rate that is used in the calculation.
+    ///
+    /// This is either the specified clock rate or, if none was specified, the
+    /// one based on the mods e.g. 1.5 for DT.
+    pub fn effective_clock_rate(&self) -> f64 {
+        self.get_clock_rate()
     }
 
     pub(crate) const fn get_mods(&self) -> u32 {
@@ -132,11 +260,16 @@ impl CatchStars {
     pub(crate) fn get_clock_rate(&self) -> f64 {
         let clock_rate = self
             .clock_rate
-            .map_or(self.mods.clock_rate() as f32, non_zero_u32_to_f32);
+            .map_or_else(|| self.get_mods_clock_rate(), non_zero_u32_to_f32);
 
         f64::from(clock_rate)
     }
 
+    fn get_mods_clock_rate(&self) -> f32 {
+        self.mods_clock_rate
+            .unwrap_or_else(|| self.mods.clock_rate() as f32)
+    }
+
     pub(crate) fn get_passed_objects(&self) -> usize {
         self.passed_objects.map_or(usize::MAX, |n| n as usize)
     }
@@ -152,12 +285,14 @@ impl Debug for CatchStars {
             mods,
             passed_objects,
             clock_rate,
+            mods_clock_rate,
         } = self;
 
         f.debug_struct("CatchStars")
             .field("mods", mods)
             .field("passed_objects", passed_objects)
             .field("clock_rate", &clock_rate.map(non_zero_u32_to_f32))
+            .field("mods_clock_rate", mods_clock_rate)
             .finish()
     }
 }
@@ -207,6 +342,12 @@ impl DifficultyValues {
 
         let palpable_objects = convert_objects(map, &mut count, hr_offsets, map_attrs.cs as f32);
 
+        attrs.n_hyper_dashes = palpable_objects
+            .iter()
+            .take(take)
+            .filter(|h| h.hyper_dash)
+            .count() as u32;
+
         let diff_objects = Self::create_difficulty_objects(
             &map_attrs,
             clock_rate,
@@ -263,3 +404,73 @@ impl DifficultyValues {
             .collect()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use rosu_pp::model::mods::rosu_mods::{
+        generated_mods::DoubleTimeCatch, GameMod, GameMods as GameModsLazer, GameModsIntermode,
+    };
+
+    use super::*;
+
+    #[test]
+    fn intermode_mods_match_bits() {
+        let intermode = CatchStars::new().mods(GameModsIntermode::from_acronyms("HDDT"));
+        let bits = CatchStars::new().mods(8 + 64);
+
+        assert_eq!(intermode.effective_mods(), bits.effective_mods());
+        assert_eq!(
+            intermode.effective_clock_rate(),
+            bits.effective_clock_rate()
+        );
+        assert_eq!(bits.effective_clock_rate(), 1.5);
+    }
+
+    #[test]
+    fn lazer_mods_keep_custom_rate() {
+        let mut mods = GameModsLazer::new();
+
+        mods.insert(GameMod::DoubleTimeCatch(DoubleTimeCatch {
+            speed_change: Some(1.2),
+            ..Default::default()
+        }));
+
+        let difficulty = CatchStars::new().mods(mods);
+
+        assert_eq!(difficulty.effective_mods(), 64);
+        assert!((difficulty.effective_clock_rate() - 1.2).abs() < 1e-6);
+
+        let difficulty = difficulty.clock_rate(1.1);
+        assert!((difficulty.effective_clock_rate() - 1.1).abs() < 1e-6);
+    }
+
+    #[test]
+    fn clock_rate_overrides_mods() {
+        let mods_only = CatchStars::new().mods(64);
+        assert_eq!(mods_only.effective_clock_rate(), 1.5);
+
+        let overridden = mods_only.clock_rate(0.9);
+        assert!((overridden.effective_clock_rate() - 0.9).abs() < 1e-6);
+
+        let reset = overridden.reset_clock_rate();
+        assert_eq!(reset.effective_clock_rate(), 1.5);
+        assert_eq!(
+            CatchStars::new().reset_clock_rate().effective_clock_rate(),
+            1.0
+        );
+    }
+
+    #[test]
+    fn non_finite_clock_rate_is_ignored() {
+        for clock_rate in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            let difficulty = CatchStars::new().clock_rate(clock_rate);
+            assert_eq!(difficulty.effective_clock_rate(), 1.0);
+
+            let difficulty = CatchStars::new().clock_rate(1.2).clock_rate(clock_rate);
+            assert_eq!(difficulty.effective_clock_rate(), 1.0);
+
+            let difficulty = CatchStars::new().mods(64).clock_rate(clock_rate);
+            assert_eq!(difficulty.effective_clock_rate(), 1.5);
+        }
+    }
+}
@@ -1,8 +1,8 @@
 use std::cmp::{self, Ordering};
 
-use rosu_pp::{catch::CatchScoreState, Beatmap};
+use rosu_pp::{catch::CatchScoreState, model::mods::GameMods, Beatmap};
 
-use crate::util::mods::Mods;
+use crate::util::{accuracy::catch_accuracy, float_ext::FloatExt, mods::Mods};
 
 use super::{CatchDifficultyAttributes, CatchPerformanceAttributes, CatchStars};
 
@@ -51,8 +51,20 @@ impl<'map> FruitsPP<'map> {
 
     /// Specify mods.
     ///
+    /// Accepted types are
+    /// - `u32`
+    /// - [`rosu_mods::GameModsLegacy`]
+    /// - [`rosu_mods::GameMods`]
+    /// - [`rosu_mods::GameModsIntermode`]
+    /// - [`&rosu_mods::GameModsIntermode`](rosu_mods::GameModsIntermode)
+    ///
+    /// Mods are converted to their legacy bit values so lazer mods without
+    /// a legacy counterpart are ignored. The custom speed change of lazer
+    /// rate changing mods is kept and used unless a clock rate is specified
+    /// through [`clock_rate`](Self::clock_rate).
+    ///
     /// See <https://github.com/ppy/osu-api/wiki#mods>
-    pub fn mods(mut self, mods: u32) -> Self {
+    pub fn mods(mut self, mods: impl Into<GameMods>) -> Self {
         self.difficulty = self.difficulty.mods(mods);
 
         self
@@ -109,10 +121,10 @@ impl<'map> FruitsPP<'map> {
     /// Amount of passed objects for partial plays, e.g. a fail.
     ///
     /// If you want to calculate the performance after every few objects,
-    /// instead of using [`CatchPerformance`] multiple times with different
+    /// instead of using [`FruitsPP`] multiple times with different
     /// `passed_objects`, you should use [`CatchGradualPerformance`].
     ///
-    /// [`CatchGradualPerformance`]: crate::catch::CatchGradualPerformance
+    /// [`CatchGradualPerformance`]: crate::fruits_2022::CatchGradualPerformance
     pub fn passed_objects(mut self, passed_objects: u32) -> Self {
         self.difficulty = self.difficulty.passed_objects(passed_objects);
 
@@ -157,8 +169,15 @@ impl<'map> FruitsPP<'map> {
 
     /// Specify the accuracy of a play between `0.0` and `100.0`.
     /// This will be used to generate matching hitresults.
+    ///
+    /// A `NaN` or infinite value is ignored, i.e. hitresults are generated as
+    /// if no accuracy was specified.
     pub fn accuracy(mut self, acc: f64) -> Self {
-        self.acc = Some(acc.clamp(0.0, 100.0) / 100.0);
+        self.acc = if !acc.is_finite() {
+            None
+        } else {
+            Some(acc.clamp(0.0, 100.0) / 100.0)
+        };
 
         self
     }
@@ -248,7 +267,7 @@ impl<'map> FruitsPP<'map> {
             for n_tiny_droplets in min_tiny_droplets..=max_tiny_droplets {
                 let n_tiny_droplet_misses = attrs.n_tiny_droplets - n_tiny_droplets;
 
-                let curr_acc = accuracy(
+                let curr_acc = catch_accuracy(
                     n_fruits,
                     n_droplets,
                     n_tiny_droplets,
@@ -394,23 +413,114 @@ impl CatchPerformanceInner {
         CatchPerformanceAttributes {
             difficulty: self.attrs,
             pp,
+            total_hits: self.total_hits(),
         }
     }
 
+    const fn total_hits(&self) -> u32 {
+        self.combo_hits() + self.state.tiny_droplets + self.state.tiny_droplet_misses
+    }
+
     const fn combo_hits(&self) -> u32 {
         self.state.fruits + self.state.droplets + self.state.misses
     }
 }
 
-fn accuracy(
-    n_fruits: u32,
-    n_droplets: u32,
-    n_tiny_droplets: u32,
-    n_tiny_droplet_misses: u32,
-    misses: u32,
-) -> f64 {
-    let numerator = n_fruits + n_droplets + n_tiny_droplets;
-    let denominator = numerator + n_tiny_droplet_misses + misses;
-
-    f64::from(numerator) / f64::from(denominator)
+/// The accuracy of the given [`CatchScoreState`] between `0.0` and `1.0`
+/// where tiny droplets are weighted by `tiny_weight` relative to fruits and
+/// droplets.
+///
+/// A weight of `1.0` results in the regular accuracy that is used for the
+/// pp calculation. Lower weights make hit or missed tiny droplets matter less.
+/// Negative or `NaN` weights are treated as `0.0`.
+pub fn weighted_accuracy(state: &CatchScoreState, tiny_weight: f64) -> f64 {
+    let tiny_weight = tiny_weight.max(0.0);
+
+    let hits = f64::from(state.fruits + state.droplets);
+    let tiny_hits = f64::from(state.tiny_droplets) * tiny_weight;
+
+    let numerator = hits + tiny_hits;
+    let denominator =
+        numerator + f64::from(state.misses) + f64::from(state.tiny_droplet_misses) * tiny_weight;
+
+    if denominator.eq(0.0) {
+        return 0.0;
+    }
+
+    numerator / denominator
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state() -> CatchScoreState {
+        CatchScoreState {
+            max_combo: 500,
+            fruits: 400,
+            droplets: 50,
+            tiny_droplets: 180,
+            tiny_droplet_misses: 20,
+            misses: 3,
+        }
+    }
+
+    #[test]
+    fn total_hits_of_state() {
+        let map = Beatmap::default();
+
+        let attrs = CatchDifficultyAttributes {
+            stars: 4.0,
+            ar: 9.0,
+            n_fruits: 403,
+            n_droplets: 50,
+            n_tiny_droplets: 200,
+            ..Default::default()
+        };
+
+        let calc = FruitsPP::new(&map)
+            .attributes(attrs.clone())
+            .accuracy(97.0)
+            .misses(3);
+        let (state, _) = calc.clone().generate_state();
+
+        let expected = state.fruits
+            + state.droplets
+            + state.tiny_droplets
+            + state.tiny_droplet_misses
+            + state.misses;
+
+        assert_eq!(expected, 653);
+        assert_eq!(calc.calculate().total_hits(), expected);
+
+        let pp = FruitsPP::new(&map)
+            .attributes(attrs)
+            .state(state())
+            .calculate();
+        assert_eq!(pp.total_hits(), 653);
+    }
+
+    #[test]
+    fn weighted_accuracy_full_weight() {
+        let state = state();
+        let expected = catch_accuracy(400, 50, 180, 20, 3);
+
+        assert!(weighted_accuracy(&state, 1.0).eq(expected));
+        assert!(weighted_accuracy(&state, 1.0).eq(state.accuracy()));
+    }
+
+    #[test]
+    fn weighted_accuracy_ignores_tiny_droplets() {
+        let state = state();
+        let expected = catch_accuracy(400, 50, 0, 0, 3);
+
+        assert!(weighted_accuracy(&state, 0.0).eq(expected));
+        assert!(weighted_accuracy(&state, -1.0).eq(expected));
+        assert!(weighted_accuracy(&state, f64::NAN).eq(expected));
+    }
+
+    #[test]
+    fn weighted_accuracy_empty() {
+        assert!(weighted_accuracy(&CatchScoreState::default(), 1.0).eq(0.0));
+    }
 }
@@ -16,7 +16,7 @@ use rosu_pp::{
     },
     Beatmap,
 };
-use std::{iter::Map, vec::IntoIter};
+use std::mem;
 
 use crate::util::{
     control_points::{difficulty_point_at, timing_point_at},
@@ -27,15 +27,40 @@ pub use self::pp::*;
 use self::{catch_object::CatchObject, difficulty_object::DifficultyObject, movement::Movement};
 
 const SECTION_LENGTH: f64 = 750.0;
-const STAR_SCALING_FACTOR: f64 = 0.145;
+/// Scales the square root of the movement difficulty value into the ppv1
+/// catch star rating.
+pub const STAR_SCALING_FACTOR: f64 = 0.145;
 
 const CATCHER_SIZE: f32 = 106.75;
 
 const LEGACY_LAST_TICK_OFFSET: f64 = 36.0;
 const BASE_SCORING_DISTANCE: f64 = 100.0;
 
+/// Buffers that are used during star calculation.
+///
+/// When calculating many maps, the same buffers can be passed to
+/// [`stars_with_bufs`] for each map so that their allocations are reused.
+#[derive(Default)]
+pub struct CatchBuffers {
+    curve: CurveBuffers,
+    ticks: Vec<(Pos, f64)>,
+    slider_objects: Vec<(Pos, f64)>,
+    objects: Vec<CatchObject>,
+}
+
 /// Star calculation for osu!ctb maps
 pub fn stars(map: &Beatmap, mods: u32) -> CatchDifficultyAttributes {
+    stars_with_bufs(map, mods, &mut CatchBuffers::default())
+}
+
+/// Star calculation for osu!ctb maps while reusing the given buffers.
+///
+/// The result is the same as for [`stars`].
+pub fn stars_with_bufs(
+    map: &Beatmap,
+    mods: u32,
+    bufs: &mut CatchBuffers,
+) -> CatchDifficultyAttributes {
     if map.hit_objects.len() < 2 {
         return CatchDifficultyAttributes::default();
     }
@@ -47,20 +72,23 @@ pub fn stars(map: &Beatmap, mods: u32) -> CatchDifficultyAttributes {
         ..Default::default()
     };
 
+    bufs.ticks.clear();
+    bufs.objects.clear();
+
     let mut params = FruitParams {
         attributes,
-        curve_bufs: CurveBuffers::default(),
+        curve_bufs: mem::take(&mut bufs.curve),
         last_pos: None,
         last_time: 0.0,
-        ticks: Vec::new(), // using the same buffer for all sliders
+        ticks: mem::take(&mut bufs.ticks), // using the same buffer for all sliders
         with_hr: mods.hr(),
     };
 
-    // BUG: Incorrect object order on 2B maps that have fruits within sliders
-    let mut hit_objects = map
-        .hit_objects
-        .iter()
-        .filter_map(|h| match &h.kind {
+    let hit_objects = &mut bufs.objects;
+    let slider_objects = &mut bufs.slider_objects;
+
+    for h in map.hit_objects.iter() {
+        match &h.kind {
             HitObjectKind::Circle => {
                 let mut h = CatchObject::new((h.pos, h.start_time));
 
@@ -70,7 +98,7 @@ pub fn stars(map: &Beatmap, mods: u32) -> CatchDifficultyAttributes {
 
                 params.attributes.n_fruits += 1;
 
-                Some(FruitOrJuice::Fruit(Some(h)))
+                hit_objects.push(h);
             }
             HitObjectKind::Slider(Slider {
                 expected_dist,
@@ -146,8 +174,8 @@ pub fn stars(map: &Beatmap, mods: u32) -> CatchDifficultyAttributes {
                     &params.ticks,
                 );
 
-                let mut slider_objects =
-                    Vec::with_capacity(span_count as usize * (params.ticks.len() + 1));
+                slider_objects.clear();
+                slider_objects.reserve(span_count as usize * (params.ticks.len() + 1));
                 slider_objects.push((h.pos, h.start_time));
 
                 // Other spans
@@ -188,15 +216,21 @@ pub fn stars(map: &Beatmap, mods: u32) -> CatchDifficultyAttributes {
                 params.attributes.n_fruits += new_fruits as u32;
                 params.attributes.n_droplets += (slider_objects.len() - new_fruits) as u32;
 
-                let iter = slider_objects
-                    .into_iter()
-                    .map(CatchObject::new as fn(_) -> _);
-
-                Some(FruitOrJuice::Juice(iter))
+                hit_objects.extend(slider_objects.drain(..).map(CatchObject::new));
             }
-            HitObjectKind::Spinner { .. } | HitObjectKind::Hold { .. } => None,
-        })
-        .flatten();
+            HitObjectKind::Spinner { .. } | HitObjectKind::Hold { .. } => {}
+        }
+    }
+
+    // Hand the buffers back for the next map
+    bufs.curve = mem::take(&mut params.curve_bufs);
+    bufs.ticks = mem::take(&mut params.ticks);
+
+    // 2B maps may have fruits within sliders so nested objects need to be
+    // put in order. The sort is stable so simultaneous objects keep their order.
+    hit_objects.sort_by(|a, b| a.time.total_cmp(&b.time));
+
+    let mut hit_objects = hit_objects.drain(..);
 
     // Hyper dash business
     let base_size = calculate_catch_width(map_attributes.cs as f32) * 0.5;
@@ -348,34 +382,6 @@ fn calculate_catch_width(cs: f32) -> f32 {
     CATCHER_SIZE * (1.0 - 0.7 * (cs - 5.0) / 5.0).abs()
 }
 
-type JuiceStream = Map<IntoIter<(Pos, f64)>, fn((Pos, f64)) -> CatchObject>;
-
-pub(crate) enum FruitOrJuice {
-    Fruit(Option<CatchObject>),
-    Juice(JuiceStream),
-}
-
-impl Iterator for FruitOrJuice {
-    type Item = CatchObject;
-
-    #[inline]
-    fn next(&mut self) -> Option<Self::Item> {
-        match self {
-            Self::Fruit(fruit) => fruit.take(),
-            Self::Juice(slider) => slider.next(),
-        }
-    }
-
-    #[inline]
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        match self {
-            Self::Fruit(Some(_)) => (1, Some(1)),
-            Self::Fruit(None) => (0, Some(0)),
-            Self::Juice(slider) => slider.size_hint(),
-        }
-    }
-}
-
 pub(crate) struct FruitParams {
     pub(crate) attributes: CatchDifficultyAttributes,
     pub(crate) curve_bufs: CurveBuffers,
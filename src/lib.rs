@@ -21,3 +21,130 @@ pub mod taiko_2022;
 pub mod taiko_ppv1;
 
 mod util;
+
+#[cfg(feature = "ffi")]
+pub mod ffi;
+
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+use rosu_pp::Beatmap;
+
+pub use util::{
+    accuracy,
+    control_points::{bpm, bpm_changes, bpm_range},
+    convert::ConvertError,
+    curve,
+    hit_windows::{hit_windows, HitWindows},
+    mods::{parse_mods, ParseModsError},
+};
+
+/// The osu!standard algorithm versions available in this crate.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum OsuVersion {
+    /// See [`osu_2014_may`].
+    May2014,
+    /// See [`osu_2014_july`].
+    July2014,
+    /// See [`osu_2015_february`].
+    Feb2015,
+    /// See [`osu_2015_april`].
+    Apr2015,
+    /// See [`osu_2018`].
+    Y2018,
+    /// See [`osu_2019`].
+    Y2019,
+    /// See [`osu_2021_january`].
+    Jan2021,
+    /// See [`osu_2021_july`].
+    Jul2021,
+    /// See [`osu_2021_november`].
+    Nov2021,
+    /// See [`osu_2022`].
+    Y2022,
+}
+
+/// Calculate the star rating of an osu!standard map with the given algorithm
+/// version.
+pub fn osu_stars(version: OsuVersion, map: &Beatmap, mods: u32) -> f64 {
+    match version {
+        OsuVersion::May2014 => osu_2014_may::stars(map, mods).stars,
+        OsuVersion::July2014 => osu_2014_july::stars(map, mods).stars,
+        OsuVersion::Feb2015 => osu_2015_february::stars(map, mods).stars,
+        OsuVersion::Apr2015 => osu_2015_april::stars(map, mods).stars,
+        OsuVersion::Y2018 => osu_2018::stars(map, mods).stars,
+        OsuVersion::Y2019 => osu_2019::stars(map, mods, None).stars,
+        OsuVersion::Jan2021 => osu_2021_january::stars(map, mods).stars,
+        OsuVersion::Jul2021 => osu_2021_july::stars(map, mods).stars,
+        OsuVersion::Nov2021 => osu_2021_november::stars(map, mods).stars,
+        OsuVersion::Y2022 => osu_2022::OsuStars::new().mods(mods).calculate(map).stars,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scaling_constants() {
+        let difficulty_multipliers = [
+            osu_2014_may::DIFFICULTY_MULTIPLIER,
+            osu_2014_july::DIFFICULTY_MULTIPLIER,
+            osu_2015_february::DIFFICULTY_MULTIPLIER,
+            osu_2015_april::DIFFICULTY_MULTIPLIER,
+            osu_2018::DIFFICULTY_MULTIPLIER,
+            osu_2019::DIFFICULTY_MULTIPLIER,
+            osu_2021_january::DIFFICULTY_MULTIPLIER,
+            osu_2021_july::DIFFICULTY_MULTIPLIER,
+            osu_2021_november::DIFFICULTY_MULTIPLIER,
+            osu_2022::DIFFICULTY_MULTIPLIER,
+            taiko_2022::DIFFICULTY_MULTIPLIER,
+        ];
+
+        assert_eq!(
+            difficulty_multipliers,
+            [0.045, 0.0675, 0.0675, 0.0675, 0.0675, 0.0675, 0.0675, 0.0675, 0.0675, 0.0675, 1.35]
+        );
+
+        let star_scaling_factors = [
+            taiko_ppv1::STAR_SCALING_FACTOR,
+            mania_ppv1::STAR_SCALING_FACTOR,
+            mania_2018::STAR_SCALING_FACTOR,
+            mania_2022::STAR_SCALING_FACTOR,
+            fruits_ppv1::STAR_SCALING_FACTOR,
+            fruits_2022::STAR_SCALING_FACTOR,
+        ];
+
+        assert_eq!(
+            star_scaling_factors,
+            [0.04125, 0.018, 0.018, 0.018, 0.145, 0.153]
+        );
+
+        let performance_multipliers = [
+            osu_2014_may::PERFORMANCE_BASE_MULTIPLIER,
+            osu_2014_july::PERFORMANCE_BASE_MULTIPLIER,
+            osu_2015_february::PERFORMANCE_BASE_MULTIPLIER,
+            osu_2015_april::PERFORMANCE_BASE_MULTIPLIER,
+            osu_2018::PERFORMANCE_BASE_MULTIPLIER,
+            osu_2019::PERFORMANCE_BASE_MULTIPLIER,
+            osu_2021_january::PERFORMANCE_BASE_MULTIPLIER,
+            osu_2021_july::PERFORMANCE_BASE_MULTIPLIER,
+            osu_2021_november::PERFORMANCE_BASE_MULTIPLIER,
+            osu_2022::PERFORMANCE_BASE_MULTIPLIER,
+            taiko_ppv1::PERFORMANCE_BASE_MULTIPLIER,
+            taiko_2020::PERFORMANCE_BASE_MULTIPLIER,
+            taiko_2022::PERFORMANCE_BASE_MULTIPLIER,
+            mania_ppv1::PERFORMANCE_BASE_MULTIPLIER,
+            mania_2018::PERFORMANCE_BASE_MULTIPLIER,
+            mania_2022::PERFORMANCE_BASE_MULTIPLIER,
+        ];
+
+        assert_eq!(
+            performance_multipliers,
+            [
+                1.1, 1.1, 1.12, 1.12, 1.12, 1.12, 1.12, 1.12, 1.12, 1.14, 1.1, 1.1, 1.13, 1.1, 0.8,
+                8.0
+            ]
+        );
+    }
+}
@@ -14,7 +14,9 @@ use strain::Strain;
 use crate::util::mods::Mods;
 
 const SECTION_LEN: f64 = 400.0;
-const STAR_SCALING_FACTOR: f64 = 0.018;
+/// The 2018 mania star rating is the strain's difficulty value scaled by this
+/// factor.
+pub const STAR_SCALING_FACTOR: f64 = 0.018;
 
 /// Difficulty calculator on osu!mania maps.
 ///
@@ -58,17 +60,28 @@ impl<'map> ManiaStars<'map> {
     }
 
     /// Calculate all difficulty related values, including stars.
+    ///
+    /// Only osu!standard and osu!mania maps are supported; for any other
+    /// mode the stars are `0.0`.
     #[inline]
     pub fn calculate(self) -> ManiaDifficultyAttributes {
-        let mut strain = calculate_strain(self);
+        let is_convert = self.map.mode != GameMode::Mania;
+
+        let Some(mut strain) = calculate_strain(self) else {
+            return ManiaDifficultyAttributes {
+                is_convert,
+                ..Default::default()
+            };
+        };
 
         ManiaDifficultyAttributes {
             stars: Strain::difficulty_value(&mut strain.strain_peaks) * STAR_SCALING_FACTOR,
+            is_convert,
         }
     }
 }
 
-fn calculate_strain(params: ManiaStars<'_>) -> Strain {
+fn calculate_strain(params: ManiaStars<'_>) -> Option<Strain> {
     let ManiaStars { map, mods } = params;
 
     let rounded_cs = map.cs.round();
@@ -98,7 +111,7 @@ fn calculate_strain(params: ManiaStars<'_>) -> Strain {
                 (rounded_od as u8 + 1).clamp(4, 7)
             }
         }
-        other => panic!("can not calculate mania difficulty on a {:?} map", other),
+        _ => return None,
     };
 
     let clock_rate = mods.clock_rate();
@@ -115,7 +128,7 @@ fn calculate_strain(params: ManiaStars<'_>) -> Strain {
     // Handle first object distinctly
     let h = match hit_objects.next() {
         Some(h) => h,
-        None => return strain,
+        None => return Some(strain),
     };
 
     // No strain for first object
@@ -135,7 +148,7 @@ fn calculate_strain(params: ManiaStars<'_>) -> Strain {
 
     strain.save_current_peak();
 
-    strain
+    Some(strain)
 }
 
 #[derive(Debug)]
@@ -163,13 +176,17 @@ impl<'o> DifficultyHitObject<'o> {
 
 /// The result of a difficulty calculation on an osu!mania map.
 #[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ManiaDifficultyAttributes {
     /// The final star rating.
     pub stars: f64,
+    /// Whether the map was a convert i.e. an osu!standard map.
+    pub is_convert: bool,
 }
 
 /// The result of a performance calculation on an osu!mania map.
 #[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ManiaPerformanceAttributes {
     /// The difficulty attributes that were used for the performance calculation
     pub difficulty: ManiaDifficultyAttributes,
@@ -200,3 +217,42 @@ impl From<ManiaPerformanceAttributes> for ManiaDifficultyAttributes {
         attributes.difficulty
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn convert_flag() {
+        let osu = Beatmap {
+            mode: GameMode::Osu,
+            ..Default::default()
+        };
+
+        let mania = Beatmap {
+            mode: GameMode::Mania,
+            ..Default::default()
+        };
+
+        assert!(ManiaStars::new(&osu).calculate().is_convert);
+        assert!(!ManiaStars::new(&mania).calculate().is_convert);
+    }
+
+    #[test]
+    fn unsupported_mode() {
+        let taiko = Beatmap {
+            mode: GameMode::Taiko,
+            ..Default::default()
+        };
+
+        let result = std::panic::catch_unwind(|| ManiaStars::new(&taiko).calculate());
+
+        let expected = ManiaDifficultyAttributes {
+            stars: 0.0,
+            is_convert: true,
+        };
+
+        assert_eq!(result.ok(), Some(expected));
+        assert_eq!(ManiaPP::new(&taiko).calculate().difficulty, expected);
+    }
+}
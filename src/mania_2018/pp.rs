@@ -1,9 +1,13 @@
-use rosu_pp::Beatmap;
+use rosu_pp::{model::mode::GameMode, Beatmap};
 
 use crate::util::mods::Mods;
 
 use super::{ManiaDifficultyAttributes, ManiaPerformanceAttributes, ManiaStars};
 
+/// The 2018 mania algorithm scales the combined strain and accuracy pp down
+/// to 80%.
+pub const PERFORMANCE_BASE_MULTIPLIER: f64 = 0.8;
+
 /// Performance calculator on osu!mania maps.
 ///
 /// # Example
@@ -100,7 +104,7 @@ impl<'map> ManiaPP<'map> {
         let mut od = 34.0 + 3.0 * (10.0 - self.map.od as f64).clamp(0.0, 10.0);
         let clock_rate = self.mods.clock_rate();
 
-        let mut multiplier = 0.8;
+        let mut multiplier = PERFORMANCE_BASE_MULTIPLIER;
 
         if nf {
             multiplier *= 0.9;
@@ -119,7 +123,10 @@ impl<'map> ManiaPP<'map> {
         let pp = (strain_value.powf(1.1) + acc_value.powf(1.1)).powf(1.0 / 1.1) * multiplier;
 
         ManiaPerformanceAttributes {
-            difficulty: ManiaDifficultyAttributes { stars },
+            difficulty: ManiaDifficultyAttributes {
+                stars,
+                is_convert: self.map.mode != GameMode::Mania,
+            },
             pp_acc: acc_value,
             pp_strain: strain_value,
             pp,
@@ -1,5 +1,8 @@
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
 /// The result of a difficulty calculation on an osu!mania map.
 #[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ManiaDifficultyAttributes {
     /// The final star rating.
     pub stars: f64,
@@ -26,6 +29,17 @@ impl ManiaDifficultyAttributes {
         self.n_objects
     }
 
+    /// Return the hit window for an n300 in milliseconds.
+    ///
+    /// The window is based on the map's OD after applying EZ/HR and is divided
+    /// by the clock rate of DT/HT, i.e. it represents the perceived window
+    /// during gameplay. This is the same value as [`hit_window`].
+    ///
+    /// [`hit_window`]: ManiaDifficultyAttributes::hit_window
+    pub const fn hit_window_great_ms(&self) -> f64 {
+        self.hit_window
+    }
+
     /// Whether the [`Beatmap`] was a convert i.e. an osu!standard map.
     ///
     /// [`Beatmap`]: crate::model::beatmap::Beatmap
@@ -36,6 +50,7 @@ impl ManiaDifficultyAttributes {
 
 /// The result of a performance calculation on an osu!mania map.
 #[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ManiaPerformanceAttributes {
     /// The difficulty attributes that were used for the performance calculation.
     pub difficulty: ManiaDifficultyAttributes,
@@ -43,6 +58,9 @@ pub struct ManiaPerformanceAttributes {
     pub pp: f64,
     /// The difficulty portion of the final pp.
     pub pp_difficulty: f64,
+    /// The amount of hitresults of the score, i.e. the sum of all judgements
+    /// including misses.
+    pub total_hits: u32,
 }
 
 impl ManiaPerformanceAttributes {
@@ -61,6 +79,14 @@ impl ManiaPerformanceAttributes {
         self.difficulty.max_combo
     }
 
+    /// Return the amount of hitresults of the score, i.e. the sum of all
+    /// judgements including misses.
+    ///
+    /// For partial plays, this only covers the passed objects.
+    pub const fn total_hits(&self) -> u32 {
+        self.total_hits
+    }
+
     /// Return the amount of hitobjects.
     pub const fn n_objects(&self) -> u32 {
         self.difficulty.n_objects
@@ -74,8 +100,45 @@ impl ManiaPerformanceAttributes {
     }
 }
 
+/// Concise summary of the pp and its components, rounded to one decimal,
+/// e.g. `pp=498.7 (difficulty=62.3)`.
+impl Display for ManiaPerformanceAttributes {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(
+            f,
+            "pp={:.1} (difficulty={:.1})",
+            self.pp, self.pp_difficulty
+        )
+    }
+}
+
 impl From<ManiaPerformanceAttributes> for ManiaDifficultyAttributes {
     fn from(attributes: ManiaPerformanceAttributes) -> Self {
         attributes.difficulty
     }
 }
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_round_trip() {
+        let attrs = ManiaPerformanceAttributes {
+            difficulty: ManiaDifficultyAttributes {
+                stars: 4.75,
+                hit_window: 40.5,
+                n_objects: 1500,
+                max_combo: 2100,
+                is_convert: true,
+            },
+            pp: 280.25,
+            pp_difficulty: 280.25,
+            total_hits: 1500,
+        };
+
+        let json = serde_json::to_string(&attrs).unwrap();
+        let deserialized: ManiaPerformanceAttributes = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, attrs);
+    }
+}
@@ -12,10 +12,16 @@ pub struct ManiaDifficultyObject {
 
 impl ManiaDifficultyObject {
     pub fn new(base: &ManiaObject, last: &ManiaObject, clock_rate: f64, idx: usize) -> Self {
+        // Chords have a delta time of zero which the strain handles just fine.
+        // Negative values on the other hand would only come from objects that
+        // are out of order and would inflate strains when decaying so we clamp
+        // them.
+        let delta_time = ((base.start_time - last.start_time) / clock_rate).max(0.0);
+
         Self {
             idx,
             base_column: base.column,
-            delta_time: (base.start_time - last.start_time) / clock_rate,
+            delta_time,
             start_time: base.start_time / clock_rate,
             end_time: base.end_time / clock_rate,
         }
@@ -27,3 +33,80 @@ impl IDifficultyObject for ManiaDifficultyObject {
         self.idx
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use rosu_pp::Beatmap;
+
+    use crate::mania_2022::ManiaStars;
+
+    use super::*;
+
+    fn note(start_time: f64, column: usize) -> ManiaObject {
+        ManiaObject {
+            start_time,
+            end_time: start_time,
+            column,
+        }
+    }
+
+    #[test]
+    fn chord_and_unordered_delta_times() {
+        let chord = ManiaDifficultyObject::new(&note(1000.0, 2), &note(1000.0, 0), 1.5, 0);
+        assert_eq!(chord.delta_time, 0.0);
+        assert_eq!(chord.base_column, 2);
+
+        let unordered = ManiaDifficultyObject::new(&note(900.0, 1), &note(1000.0, 3), 1.0, 1);
+        assert_eq!(unordered.delta_time, 0.0);
+
+        let regular = ManiaDifficultyObject::new(&note(1150.0, 1), &note(1000.0, 3), 1.5, 2);
+        assert!((regular.delta_time - 100.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn simultaneous_notes_across_columns() {
+        let hit_objects: String = (0..30)
+            .flat_map(|i| {
+                let start_time = 1000 + i * 200;
+
+                (0..4).map(move |column| {
+                    let x = 64 + column * 128;
+
+                    format!("{x},192,{start_time},1,0,0:0:0:0:\n")
+                })
+            })
+            .collect();
+
+        let content = format!(
+            "osu file format v14
+
+[General]
+Mode: 3
+
+[Difficulty]
+HPDrainRate:5
+CircleSize:4
+OverallDifficulty:8
+ApproachRate:5
+SliderMultiplier:1.4
+SliderTickRate:1
+
+[TimingPoints]
+0,300,4,2,0,100,1,0
+
+[HitObjects]
+{hit_objects}"
+        );
+
+        let map = Beatmap::from_bytes(content.as_bytes()).unwrap();
+
+        let attrs = ManiaStars::new().calculate(&map);
+        assert!(attrs.stars.is_finite());
+        assert!(attrs.stars > 0.0);
+        assert_eq!(attrs.n_objects, 120);
+
+        let strains = ManiaStars::new().strains_per_column(&map);
+        assert_eq!(strains.len(), 4);
+        assert!(strains.iter().flatten().all(|strain| strain.is_finite()));
+    }
+}
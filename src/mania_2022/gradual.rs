@@ -0,0 +1,224 @@
+use std::cmp;
+
+use rosu_pp::{model::mode::GameMode, Beatmap};
+
+use crate::util::{
+    convert::{convert_ref, ConvertError},
+    skills::Skill,
+};
+
+use super::{
+    difficulty_object::ManiaDifficultyObject,
+    mania_object::{ManiaObject, ObjectParams},
+    strain::Strain,
+    DifficultyValues, ManiaDifficultyAttributes, ManiaStars, STAR_SCALING_FACTOR,
+};
+
+/// Gradually calculate the difficulty attributes of an osu!mania map.
+///
+/// Note that this struct implements [`Iterator`].
+/// On every call of [`Iterator::next`], the map's next hit object will
+/// be processed and the [`ManiaDifficultyAttributes`] will be updated and
+/// returned.
+pub struct ManiaGradualDifficulty {
+    idx: usize,
+    attrs: ManiaDifficultyAttributes,
+    /// The maximum combo after each hit object.
+    max_combos: Box<[u32]>,
+    diff_objects: Box<[ManiaDifficultyObject]>,
+    strain: Strain,
+}
+
+impl ManiaGradualDifficulty {
+    /// Create a [`ManiaGradualDifficulty`] for a map.
+    ///
+    /// If the map cannot be converted to osu!mania, the iterator will not
+    /// yield any attributes. Use [`checked_new`](Self::checked_new) to handle
+    /// that case explicitly.
+    pub fn new(difficulty: ManiaStars, map: &Beatmap) -> Self {
+        Self::checked_new(difficulty, map).unwrap_or_else(|_| Self {
+            idx: 0,
+            attrs: ManiaDifficultyAttributes::default(),
+            max_combos: Box::default(),
+            diff_objects: Box::default(),
+            strain: Strain::new(1),
+        })
+    }
+
+    /// Create a [`ManiaGradualDifficulty`] for a map.
+    ///
+    /// Returns an error if the map cannot be converted to osu!mania.
+    pub fn checked_new(difficulty: ManiaStars, map: &Beatmap) -> Result<Self, ConvertError> {
+        let map = convert_ref(map, GameMode::Mania, &difficulty.get_mods().into())?;
+        let map = map.as_ref();
+
+        let take = cmp::min(difficulty.get_passed_objects(), map.hit_objects.len());
+        let total_columns = map.cs.round_ties_even().max(1.0);
+        let clock_rate = difficulty.get_clock_rate();
+        let mut params = ObjectParams::new(map);
+        let mut max_combos = Vec::with_capacity(take);
+
+        let mania_objects: Vec<_> = map
+            .hit_objects
+            .iter()
+            .take(take)
+            .map(|h| {
+                let obj = ManiaObject::new(h, total_columns, &mut params);
+                max_combos.push(params.max_combo());
+
+                obj
+            })
+            .collect();
+
+        let diff_objects =
+            DifficultyValues::create_difficulty_objects(clock_rate, mania_objects.into_iter());
+
+        let hit_window = map
+            .attributes()
+            .mods(difficulty.get_mods())
+            .hit_windows()
+            .od_great;
+
+        let attrs = ManiaDifficultyAttributes {
+            hit_window,
+            is_convert: map.is_convert,
+            ..Default::default()
+        };
+
+        Ok(Self {
+            idx: 0,
+            attrs,
+            max_combos: max_combos.into_boxed_slice(),
+            diff_objects,
+            strain: Strain::new(total_columns as usize),
+        })
+    }
+
+    /// Process the next hit object without evaluating the difficulty.
+    ///
+    /// Returns `false` if there are no objects left.
+    fn process_next(&mut self) -> bool {
+        let Some(&max_combo) = self.max_combos.get(self.idx) else {
+            return false;
+        };
+
+        // The first hit object has no difficulty object
+        if let Some(curr) = self
+            .idx
+            .checked_sub(1)
+            .and_then(|i| self.diff_objects.get(i))
+        {
+            Skill::new(&mut self.strain, &self.diff_objects).process(curr);
+        }
+
+        self.idx += 1;
+        self.attrs.max_combo = max_combo;
+        self.attrs.n_objects = self.idx as u32;
+
+        true
+    }
+}
+
+impl Iterator for ManiaGradualDifficulty {
+    type Item = ManiaDifficultyAttributes;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.process_next() {
+            return None;
+        }
+
+        let mut attrs = self.attrs.clone();
+        attrs.stars = self.strain.as_difficulty_value() * STAR_SCALING_FACTOR;
+
+        Some(attrs)
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        for _ in 0..cmp::min(n, self.len()) {
+            self.process_next();
+        }
+
+        self.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for ManiaGradualDifficulty {
+    fn len(&self) -> usize {
+        self.max_combos.len() - self.idx
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn map() -> Beatmap {
+        let columns = [64, 192, 320, 448];
+
+        let mut hit_objects: String = (0..40)
+            .map(|i| {
+                format!(
+                    "{},192,{},1,0,0:0:0:0:\n",
+                    columns[(i * 3) % 4],
+                    500 + i * 150
+                )
+            })
+            .collect();
+
+        hit_objects.push_str("192,192,6600,128,0,7200:0:0:0:0:\n");
+        hit_objects.push_str("448,192,6600,1,0,0:0:0:0:\n");
+
+        let content = format!(
+            "osu file format v14
+
+[General]
+Mode: 3
+
+[Difficulty]
+HPDrainRate:5
+CircleSize:4
+OverallDifficulty:8
+ApproachRate:9
+SliderMultiplier:1.4
+SliderTickRate:1
+
+[TimingPoints]
+0,300,4,2,0,100,1,0
+
+[HitObjects]
+{hit_objects}"
+        );
+
+        Beatmap::from_bytes(content.as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn last_matches_regular_calculation() {
+        let map = map();
+        let difficulty = ManiaStars::new().mods(64);
+        let expected = difficulty.calculate(&map);
+
+        let gradual = ManiaGradualDifficulty::new(difficulty, &map);
+        assert_eq!(gradual.len(), map.hit_objects.len());
+
+        let mut last: Option<ManiaDifficultyAttributes> = None;
+
+        for (i, attrs) in gradual.enumerate() {
+            assert_eq!(attrs.n_objects, i as u32 + 1);
+
+            if let Some(ref prev) = last {
+                assert!(attrs.max_combo >= prev.max_combo);
+            }
+
+            last = Some(attrs);
+        }
+
+        assert_eq!(last, Some(expected));
+    }
+}
@@ -96,6 +96,10 @@ impl<'a> ObjectParams<'a> {
         }
     }
 
+    pub const fn max_combo(&self) -> u32 {
+        self.max_combo
+    }
+
     pub fn into_max_combo(self) -> u32 {
         self.max_combo
     }
@@ -6,28 +6,45 @@ use std::{
 
 use difficulty_object::ManiaDifficultyObject;
 use mania_object::{ManiaObject, ObjectParams};
-use rosu_pp::{model::mode::GameMode, Beatmap};
+use rosu_pp::{
+    model::{mode::GameMode, mods::GameMods},
+    Beatmap,
+};
 use strain::Strain;
 
-use crate::util::{mods::Mods, skills::Skill};
+use crate::util::{
+    convert::{convert_ref, ConvertError},
+    mods::{lazer_clock_rate, legacy_bits, Mods},
+    skills::Skill,
+};
 
 pub use self::{
     attributes::{ManiaDifficultyAttributes, ManiaPerformanceAttributes},
+    gradual::ManiaGradualDifficulty,
     pp::*,
 };
 
 mod attributes;
 mod difficulty_object;
+mod gradual;
 mod mania_object;
 mod pp;
 mod strain;
 
+/// Scales the strain's difficulty value into the star rating, the same factor
+/// as in the earlier mania versions.
+pub const STAR_SCALING_FACTOR: f64 = 0.018;
+
 #[derive(Clone, PartialEq)]
 #[must_use]
 pub struct ManiaStars {
     mods: u32,
     passed_objects: Option<u32>,
     clock_rate: Option<NonZeroU32>,
+    /// Clock rate of lazer mods, including custom speed changes.
+    ///
+    /// Only used if no clock rate was specified explicitly.
+    mods_clock_rate: Option<f32>,
 }
 
 impl ManiaStars {
@@ -37,6 +54,7 @@ impl ManiaStars {
             mods: 0,
             passed_objects: None,
             clock_rate: None,
+            mods_clock_rate: None,
         }
     }
 
@@ -49,9 +67,20 @@ impl ManiaStars {
     /// - [`rosu_mods::GameModsIntermode`]
     /// - [`&rosu_mods::GameModsIntermode`](rosu_mods::GameModsIntermode)
     ///
+    /// Mods are converted to their legacy bit values so lazer mods without
+    /// a legacy counterpart are ignored. The custom speed change of lazer
+    /// rate changing mods is kept and used unless a clock rate is specified
+    /// through [`clock_rate`](Self::clock_rate).
+    ///
     /// See <https://github.com/ppy/osu-api/wiki#mods>
-    pub const fn mods(self, mods: u32) -> Self {
-        Self { mods, ..self }
+    pub fn mods(self, mods: impl Into<GameMods>) -> Self {
+        let mods = mods.into();
+
+        Self {
+            mods: legacy_bits(&mods),
+            mods_clock_rate: lazer_clock_rate(&mods).map(|clock_rate| clock_rate as f32),
+            ..self
+        }
     }
 
     /// Amount of passed objects for partial plays, e.g. a fail.
@@ -66,10 +95,24 @@ impl ManiaStars {
     /// If none is specified, it will take the clock rate based on the mods
     /// i.e. 1.5 for DT, 0.75 for HT and 1.0 otherwise.
     ///
+    /// A specified clock rate always takes precedence over the mods, e.g.
+    /// DT with a clock rate of 0.9 uses 0.9. Use
+    /// [`reset_clock_rate`](Self::reset_clock_rate) to fall back to the mods.
+    ///
     /// | Minimum | Maximum |
     /// | :-----: | :-----: |
     /// | 0.01    | 100     |
+    ///
+    /// A `NaN` or infinite value is ignored, i.e. the clock rate based on the
+    /// mods is used.
     pub fn clock_rate(self, clock_rate: f64) -> Self {
+        if !clock_rate.is_finite() {
+            return Self {
+                clock_rate: None,
+                ..self
+            };
+        }
+
         let clock_rate = (clock_rate as f32).clamp(0.01, 100.0).to_bits();
 
         // SAFETY: The minimum value is 0.01 so its bits can never be fully
@@ -82,13 +125,28 @@ impl ManiaStars {
         }
     }
 
+    /// Remove a previously specified clock rate so that the clock rate based
+    /// on the mods is used again.
+    pub const fn reset_clock_rate(mut self) -> Self {
+        self.clock_rate = None;
+
+        self
+    }
+
     /// Perform the difficulty calculation.
+    ///
+    /// If the map cannot be converted to the required mode, default
+    /// attributes are returned. Use [`try_calculate`](Self::try_calculate)
+    /// to handle that case explicitly.
     pub fn calculate(&self, map: &Beatmap) -> ManiaDifficultyAttributes {
-        const STAR_SCALING_FACTOR: f64 = 0.018;
+        self.try_calculate(map).unwrap_or_default()
+    }
 
-        let Ok(map) = map.convert_ref(GameMode::Mania, &self.mods.into()) else {
-            return ManiaDifficultyAttributes::default();
-        };
+    /// Perform the difficulty calculation.
+    ///
+    /// Returns an error if the map cannot be converted to the required mode.
+    pub fn try_calculate(&self, map: &Beatmap) -> Result<ManiaDifficultyAttributes, ConvertError> {
+        let map = convert_ref(map, GameMode::Mania, &self.mods.into())?;
 
         let difficulty = self;
         let map = map.as_ref();
@@ -103,13 +161,43 @@ impl ManiaStars {
             .hit_windows()
             .od_great;
 
-        ManiaDifficultyAttributes {
+        Ok(ManiaDifficultyAttributes {
             stars: values.strain.difficulty_value() * STAR_SCALING_FACTOR,
             hit_window,
             max_combo: values.max_combo,
             n_objects,
             is_convert: map.is_convert,
-        }
+        })
+    }
+
+    /// Perform the difficulty calculation but instead of evaluating the
+    /// strain, return the individual strain peaks of each column.
+    ///
+    /// The outer list is indexed by column, the inner list by sections of
+    /// 400ms each, adjusted by the clock rate.
+    ///
+    /// Suitable to compare the difficulty of columns over time.
+    pub fn strains_per_column(&self, map: &Beatmap) -> Vec<Vec<f64>> {
+        let Ok(map) = convert_ref(map, GameMode::Mania, &self.mods.into()) else {
+            return Vec::new();
+        };
+
+        DifficultyValues::calculate_with(self, map.as_ref(), Strain::with_column_peaks)
+            .strain
+            .get_curr_column_strain_peaks()
+    }
+
+    /// The mods' bit values that are used in the calculation.
+    pub const fn effective_mods(&self) -> u32 {
+        self.mods
+    }
+
+    /// The clock rate that is used in the calculation.
+    ///
+    /// This is either the specified clock rate or, if none was specified, the
+    /// one based on the mods e.g. 1.5 for DT.
+    pub fn effective_clock_rate(&self) -> f64 {
+        self.get_clock_rate()
     }
 
     pub(crate) const fn get_mods(&self) -> u32 {
@@ -119,11 +207,16 @@ impl ManiaStars {
     pub(crate) fn get_clock_rate(&self) -> f64 {
         let clock_rate = self
             .clock_rate
-            .map_or(self.mods.clock_rate() as f32, non_zero_u32_to_f32);
+            .map_or_else(|| self.get_mods_clock_rate(), non_zero_u32_to_f32);
 
         f64::from(clock_rate)
     }
 
+    fn get_mods_clock_rate(&self) -> f32 {
+        self.mods_clock_rate
+            .unwrap_or_else(|| self.mods.clock_rate() as f32)
+    }
+
     pub(crate) fn get_passed_objects(&self) -> usize {
         self.passed_objects.map_or(usize::MAX, |n| n as usize)
     }
@@ -145,12 +238,14 @@ impl Debug for ManiaStars {
             mods,
             passed_objects,
             clock_rate,
+            mods_clock_rate,
         } = self;
 
         f.debug_struct("ManiaStars")
             .field("mods", mods)
             .field("passed_objects", passed_objects)
             .field("clock_rate", &clock_rate.map(non_zero_u32_to_f32))
+            .field("mods_clock_rate", mods_clock_rate)
             .finish()
     }
 }
@@ -162,6 +257,14 @@ pub struct DifficultyValues {
 
 impl DifficultyValues {
     pub fn calculate(difficulty: &ManiaStars, map: &Beatmap) -> Self {
+        Self::calculate_with(difficulty, map, Strain::new)
+    }
+
+    fn calculate_with(
+        difficulty: &ManiaStars,
+        map: &Beatmap,
+        new_strain: fn(usize) -> Strain,
+    ) -> Self {
         let take = difficulty.get_passed_objects();
         let total_columns = map.cs.round_ties_even().max(1.0);
         let clock_rate = difficulty.get_clock_rate();
@@ -175,7 +278,7 @@ impl DifficultyValues {
 
         let diff_objects = Self::create_difficulty_objects(clock_rate, mania_objects);
 
-        let mut strain = Strain::new(total_columns as usize);
+        let mut strain = new_strain(total_columns as usize);
 
         {
             let mut strain = Skill::new(&mut strain, &diff_objects);
@@ -216,3 +319,73 @@ impl DifficultyValues {
         diff_objects.into_boxed_slice()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use rosu_pp::model::mods::rosu_mods::{
+        generated_mods::DoubleTimeMania, GameMod, GameMods as GameModsLazer, GameModsIntermode,
+    };
+
+    use super::*;
+
+    #[test]
+    fn intermode_mods_match_bits() {
+        let intermode = ManiaStars::new().mods(GameModsIntermode::from_acronyms("HDDT"));
+        let bits = ManiaStars::new().mods(8 + 64);
+
+        assert_eq!(intermode.effective_mods(), bits.effective_mods());
+        assert_eq!(
+            intermode.effective_clock_rate(),
+            bits.effective_clock_rate()
+        );
+        assert_eq!(bits.effective_clock_rate(), 1.5);
+    }
+
+    #[test]
+    fn lazer_mods_keep_custom_rate() {
+        let mut mods = GameModsLazer::new();
+
+        mods.insert(GameMod::DoubleTimeMania(DoubleTimeMania {
+            speed_change: Some(1.2),
+            ..Default::default()
+        }));
+
+        let difficulty = ManiaStars::new().mods(mods);
+
+        assert_eq!(difficulty.effective_mods(), 64);
+        assert!((difficulty.effective_clock_rate() - 1.2).abs() < 1e-6);
+
+        let difficulty = difficulty.clock_rate(1.1);
+        assert!((difficulty.effective_clock_rate() - 1.1).abs() < 1e-6);
+    }
+
+    #[test]
+    fn clock_rate_overrides_mods() {
+        let mods_only = ManiaStars::new().mods(64);
+        assert_eq!(mods_only.effective_clock_rate(), 1.5);
+
+        let overridden = mods_only.clock_rate(0.9);
+        assert!((overridden.effective_clock_rate() - 0.9).abs() < 1e-6);
+
+        let reset = overridden.reset_clock_rate();
+        assert_eq!(reset.effective_clock_rate(), 1.5);
+        assert_eq!(
+            ManiaStars::new().reset_clock_rate().effective_clock_rate(),
+            1.0
+        );
+    }
+
+    #[test]
+    fn non_finite_clock_rate_is_ignored() {
+        for clock_rate in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            let difficulty = ManiaStars::new().clock_rate(clock_rate);
+            assert_eq!(difficulty.effective_clock_rate(), 1.0);
+
+            let difficulty = ManiaStars::new().clock_rate(1.2).clock_rate(clock_rate);
+            assert_eq!(difficulty.effective_clock_rate(), 1.0);
+
+            let difficulty = ManiaStars::new().mods(64).clock_rate(clock_rate);
+            assert_eq!(difficulty.effective_clock_rate(), 1.5);
+        }
+    }
+}
@@ -1,8 +1,11 @@
 use std::cmp;
 
-use rosu_pp::{any::HitResultPriority, mania::ManiaScoreState, Beatmap};
+use rosu_pp::{any::HitResultPriority, mania::ManiaScoreState, model::mods::GameMods, Beatmap};
 
-use crate::util::mods::Mods;
+use crate::util::{
+    accuracy::{mania_accuracy, mania_lazer_accuracy},
+    mods::Mods,
+};
 
 use super::{ManiaDifficultyAttributes, ManiaPerformanceAttributes, ManiaStars};
 
@@ -21,6 +24,7 @@ pub struct ManiaPP<'map> {
     misses: Option<u32>,
     acc: Option<f64>,
     hitresult_priority: HitResultPriority,
+    classic_accuracy: bool,
 }
 
 impl<'map> ManiaPP<'map> {
@@ -37,6 +41,7 @@ impl<'map> ManiaPP<'map> {
             misses: None,
             acc: None,
             hitresult_priority: HitResultPriority::default(),
+            classic_accuracy: true,
         }
     }
 
@@ -52,8 +57,20 @@ impl<'map> ManiaPP<'map> {
 
     /// Specify mods.
     ///
+    /// Accepted types are
+    /// - `u32`
+    /// - [`rosu_mods::GameModsLegacy`]
+    /// - [`rosu_mods::GameMods`]
+    /// - [`rosu_mods::GameModsIntermode`]
+    /// - [`&rosu_mods::GameModsIntermode`](rosu_mods::GameModsIntermode)
+    ///
+    /// Mods are converted to their legacy bit values so lazer mods without
+    /// a legacy counterpart are ignored. The custom speed change of lazer
+    /// rate changing mods is kept and used unless a clock rate is specified
+    /// through [`clock_rate`](Self::clock_rate).
+    ///
     /// See <https://github.com/ppy/osu-api/wiki#mods>
-    pub fn mods(mut self, mods: u32) -> Self {
+    pub fn mods(mut self, mods: impl Into<GameMods>) -> Self {
         self.difficulty = self.difficulty.mods(mods);
 
         self
@@ -95,8 +112,15 @@ impl<'map> ManiaPP<'map> {
 
     /// Specify the accuracy of a play between `0.0` and `100.0`.
     /// This will be used to generate matching hitresults.
+    ///
+    /// A `NaN` or infinite value is ignored, i.e. hitresults are generated as
+    /// if no accuracy was specified.
     pub fn accuracy(mut self, acc: f64) -> Self {
-        self.acc = Some(acc.clamp(0.0, 100.0) / 100.0);
+        self.acc = if !acc.is_finite() {
+            None
+        } else {
+            Some(acc.clamp(0.0, 100.0) / 100.0)
+        };
 
         self
     }
@@ -110,6 +134,25 @@ impl<'map> ManiaPP<'map> {
         self
     }
 
+    /// Whether the accuracy should be evaluated like in osu!stable, i.e. 320s
+    /// and 300s are weighted equally, or like in osu!lazer where 320s are
+    /// weighted slightly higher.
+    ///
+    /// This only affects how hitresults are generated for a specified
+    /// [`accuracy`]; the pp itself always uses its own custom accuracy.
+    /// With osu!lazer weighting and neither 320s nor 300s specified, the
+    /// 320s and 300s are split such that the osu!lazer accuracy matches the
+    /// specified accuracy as closely as possible.
+    ///
+    /// Defaults to `true`.
+    ///
+    /// [`accuracy`]: ManiaPP::accuracy
+    pub const fn classic_accuracy(mut self, classic: bool) -> Self {
+        self.classic_accuracy = classic;
+
+        self
+    }
+
     /// Specify the amount of 320s of a play.
     pub const fn n320(mut self, n320: u32) -> Self {
         self.n320 = Some(n320);
@@ -184,6 +227,7 @@ impl<'map> ManiaPP<'map> {
         let n_objects = cmp::min(self.difficulty.get_passed_objects() as u32, attrs.n_objects);
 
         let priority = self.hitresult_priority;
+        let classic = self.classic_accuracy;
 
         let misses = self.misses.map_or(0, |n| cmp::min(n, n_objects));
         let n_remaining = n_objects - misses;
@@ -264,7 +308,7 @@ impl<'map> ManiaPP<'map> {
                     for new3x0 in min_n3x0..=max_n3x0 {
                         let new200 = n_remaining.saturating_sub(new3x0 + n100 + n50);
                         let curr_dist =
-                            (acc - accuracy(new3x0, 0, new200, n100, n50, misses)).abs();
+                            (acc - mania_accuracy(new3x0, 0, new200, n100, n50, misses)).abs();
 
                         if curr_dist < best_dist {
                             best_dist = curr_dist;
@@ -309,7 +353,7 @@ impl<'map> ManiaPP<'map> {
                     for new3x0 in min_n3x0..=max_n3x0 {
                         let new100 = n_remaining.saturating_sub(new3x0 + n200 + n50);
                         let curr_dist =
-                            (acc - accuracy(new3x0, 0, n200, new100, n50, misses)).abs();
+                            (acc - mania_accuracy(new3x0, 0, n200, new100, n50, misses)).abs();
 
                         if curr_dist < best_dist {
                             best_dist = curr_dist;
@@ -354,7 +398,7 @@ impl<'map> ManiaPP<'map> {
                     for new3x0 in min_n3x0..=max_n3x0 {
                         let new50 = n_remaining.saturating_sub(new3x0 + n200 + n100);
                         let curr_dist =
-                            (acc - accuracy(new3x0, 0, n200, n100, new50, misses)).abs();
+                            (acc - mania_accuracy(new3x0, 0, n200, n100, new50, misses)).abs();
 
                         if curr_dist < best_dist {
                             best_dist = curr_dist;
@@ -413,8 +457,9 @@ impl<'map> ManiaPP<'map> {
 
                         for new100 in min_n100..=max_n100 {
                             let new50 = n_remaining.saturating_sub(new3x0 + n200 + new100);
-                            let curr_dist =
-                                (acc - accuracy(new3x0, 0, n200, new100, new50, misses)).abs();
+                            let curr_dist = (acc
+                                - mania_accuracy(new3x0, 0, n200, new100, new50, misses))
+                            .abs();
 
                             if curr_dist < best_dist {
                                 best_dist = curr_dist;
@@ -476,8 +521,9 @@ impl<'map> ManiaPP<'map> {
 
                         for new200 in min_n200..=max_n200 {
                             let new50 = n_remaining.saturating_sub(new3x0 + new200 + n100);
-                            let curr_dist =
-                                (acc - accuracy(new3x0, 0, new200, n100, new50, misses)).abs();
+                            let curr_dist = (acc
+                                - mania_accuracy(new3x0, 0, new200, n100, new50, misses))
+                            .abs();
 
                             if curr_dist < best_dist {
                                 best_dist = curr_dist;
@@ -540,8 +586,9 @@ impl<'map> ManiaPP<'map> {
 
                         for new200 in min_n200..=max_n200 {
                             let new100 = n_remaining.saturating_sub(new3x0 + new200 + n50);
-                            let curr_dist =
-                                (acc - accuracy(new3x0, 0, new200, new100, n50, misses)).abs();
+                            let curr_dist = (acc
+                                - mania_accuracy(new3x0, 0, new200, new100, n50, misses))
+                            .abs();
 
                             if curr_dist < best_dist {
                                 best_dist = curr_dist;
@@ -623,7 +670,8 @@ impl<'map> ManiaPP<'map> {
 
                             for new100 in min_n100..=max_n100 {
                                 let new50 = n_remaining - new3x0 - new200 - new100;
-                                let curr_acc = accuracy(new3x0, 0, new200, new100, new50, misses);
+                                let curr_acc =
+                                    mania_accuracy(new3x0, 0, new200, new100, new50, misses);
                                 let curr_dist = (acc - curr_acc).abs();
 
                                 if curr_dist < best_dist {
@@ -658,6 +706,31 @@ impl<'map> ManiaPP<'map> {
                     }
                 }
             }
+
+            // * The search above uses classic weighting for which 320s and
+            // * 300s are interchangeable so their split is decided here
+            if !classic && self.n320.is_none() && self.n300.is_none() {
+                let n3x0 = n320 + n300;
+                let others = 200 * n200 + 100 * n100 + 50 * n50;
+
+                let raw_n320 =
+                    (acc * f64::from(305 * n_objects) - f64::from(300 * n3x0 + others)) / 5.0;
+
+                let mut best_dist = f64::INFINITY;
+
+                for new320 in [raw_n320.floor(), raw_n320.ceil()] {
+                    let new320 = cmp::min(new320.max(0.0) as u32, n3x0);
+                    let new300 = n3x0 - new320;
+                    let curr_dist =
+                        (acc - mania_lazer_accuracy(new320, new300, n200, n100, n50, misses)).abs();
+
+                    if curr_dist < best_dist {
+                        best_dist = curr_dist;
+                        n320 = new320;
+                        n300 = new300;
+                    }
+                }
+            }
         } else {
             let remaining = n_objects.saturating_sub(n320 + n300 + n200 + n100 + n50 + misses);
 
@@ -709,8 +782,60 @@ impl<'map> ManiaPP<'map> {
 
         inner.calculate()
     }
+
+    /// Find the accuracy between `0.0` and `100.0` that is required to reach
+    /// the given pp value.
+    ///
+    /// Specified misses are kept while other hitresults are generated based on
+    /// the accuracy. Returns `None` if the pp value can't be reached, even
+    /// with 100% accuracy.
+    pub fn accuracy_for_pp(mut self, target_pp: f64) -> Option<f64> {
+        let attrs = self
+            .attributes
+            .take()
+            .unwrap_or_else(|| self.difficulty.calculate(self.map));
+
+        self.n320 = None;
+        self.n300 = None;
+        self.n200 = None;
+        self.n100 = None;
+        self.n50 = None;
+
+        let pp_at = |acc: f64| {
+            self.clone()
+                .attributes(attrs.clone())
+                .accuracy(acc)
+                .calculate()
+                .pp
+        };
+
+        if pp_at(100.0) < target_pp {
+            return None;
+        }
+
+        let mut low = 0.0;
+        let mut high = 100.0;
+
+        for _ in 0..32 {
+            let mid = (low + high) / 2.0;
+
+            if pp_at(mid) < target_pp {
+                low = mid;
+            } else {
+                high = mid;
+            }
+        }
+
+        Some(high)
+    }
 }
 
+/// The 2022 mania pp are this multiple of the difficulty value. Accuracy is
+/// part of the difficulty value instead of a separate portion.
+// * Arbitrary initial value for scaling pp in order to standardize distributions across game modes.
+// * The specific number has no intrinsic meaning and can be adjusted as needed.
+pub const PERFORMANCE_BASE_MULTIPLIER: f64 = 8.0;
+
 struct ManiaPerformanceInner {
     attrs: ManiaDifficultyAttributes,
     mods: u32,
@@ -719,9 +844,7 @@ struct ManiaPerformanceInner {
 
 impl ManiaPerformanceInner {
     fn calculate(self) -> ManiaPerformanceAttributes {
-        // * Arbitrary initial value for scaling pp in order to standardize distributions across game modes.
-        // * The specific number has no intrinsic meaning and can be adjusted as needed.
-        let mut multiplier = 8.0;
+        let mut multiplier = PERFORMANCE_BASE_MULTIPLIER;
 
         if self.mods.nf() {
             multiplier *= 0.75;
@@ -738,6 +861,7 @@ impl ManiaPerformanceInner {
             difficulty: self.attrs,
             pp,
             pp_difficulty: difficulty_value,
+            total_hits: self.state.total_hits(),
         }
     }
 
@@ -781,9 +905,110 @@ fn custom_accuracy(n320: u32, n300: u32, n200: u32, n100: u32, n50: u32, total_h
     f64::from(numerator) / f64::from(denominator)
 }
 
-fn accuracy(n320: u32, n300: u32, n200: u32, n100: u32, n50: u32, misses: u32) -> f64 {
-    let numerator = 6 * (n320 + n300) + 4 * n200 + 2 * n100 + n50;
-    let denominator = 6 * (n320 + n300 + n200 + n100 + n50 + misses);
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    f64::from(numerator) / f64::from(denominator)
+    fn generated_accuracy(classic: bool, acc: f64, priority: HitResultPriority) -> f64 {
+        let map = Beatmap::default();
+
+        let attrs = ManiaDifficultyAttributes {
+            n_objects: 1000,
+            ..Default::default()
+        };
+
+        let (state, _) = ManiaPP::new(&map)
+            .attributes(attrs)
+            .classic_accuracy(classic)
+            .hitresult_priority(priority)
+            .accuracy(acc)
+            .generate_state();
+
+        let ManiaScoreState {
+            n320,
+            n300,
+            n200,
+            n100,
+            n50,
+            misses,
+        } = state;
+
+        assert_eq!(n320 + n300 + n200 + n100 + n50 + misses, 1000);
+
+        let acc = if classic {
+            mania_accuracy(n320, n300, n200, n100, n50, misses)
+        } else {
+            mania_lazer_accuracy(n320, n300, n200, n100, n50, misses)
+        };
+
+        acc * 100.0
+    }
+
+    #[test]
+    fn accuracy_for_pp_round_trip() {
+        let map = Beatmap::default();
+
+        let attrs = ManiaDifficultyAttributes {
+            stars: 4.5,
+            n_objects: 1500,
+            ..Default::default()
+        };
+
+        let calc = ManiaPP::new(&map).attributes(attrs.clone());
+        let max_pp = calc.clone().accuracy(100.0).calculate().pp;
+
+        for target_pp in [0.5 * max_pp, 0.8 * max_pp, 0.95 * max_pp] {
+            let acc = calc.clone().accuracy_for_pp(target_pp).unwrap();
+            let pp = ManiaPP::new(&map)
+                .attributes(attrs.clone())
+                .accuracy(acc)
+                .calculate()
+                .pp;
+
+            assert!((pp - target_pp).abs() < 0.5, "target={target_pp} pp={pp}");
+        }
+
+        assert_eq!(calc.accuracy_for_pp(max_pp + 1.0), None);
+    }
+
+    #[test]
+    fn total_hits_of_passed_objects() {
+        let map = Beatmap::default();
+
+        let attrs = ManiaDifficultyAttributes {
+            stars: 3.0,
+            n_objects: 1000,
+            ..Default::default()
+        };
+
+        let pp = ManiaPP::new(&map).attributes(attrs.clone()).calculate();
+        assert_eq!(pp.total_hits(), 1000);
+
+        let calc = ManiaPP::new(&map)
+            .attributes(attrs)
+            .passed_objects(100)
+            .misses(2);
+
+        let (state, _) = calc.clone().generate_state();
+        assert_eq!(state.total_hits(), 100);
+        assert_eq!(calc.calculate().total_hits(), 100);
+    }
+
+    #[test]
+    fn generated_state_matches_accuracy() {
+        let priorities = [HitResultPriority::BestCase, HitResultPriority::WorstCase];
+
+        for classic in [true, false] {
+            for priority in priorities {
+                for acc in [85.0, 92.34, 97.5, 99.9] {
+                    let generated = generated_accuracy(classic, acc, priority);
+
+                    assert!(
+                        (generated - acc).abs() < 0.05,
+                        "classic={classic} priority={priority:?}: expected {acc}, got {generated}"
+                    );
+                }
+            }
+        }
+    }
 }
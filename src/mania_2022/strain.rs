@@ -19,6 +19,8 @@ pub struct Strain {
     end_times: Box<[f64]>,
     individual_strains: Box<[f64]>,
 
+    column_peaks: Option<ColumnStrainPeaks>,
+
     individual_strain: f64,
     overall_strain: f64,
 
@@ -31,16 +33,35 @@ impl Strain {
             start_times: vec![0.0; total_columns].into_boxed_slice(),
             end_times: vec![0.0; total_columns].into_boxed_slice(),
             individual_strains: vec![0.0; total_columns].into_boxed_slice(),
+            column_peaks: None,
             individual_strain: 0.0,
             overall_strain: 1.0,
             inner: StrainDecaySkill::default(),
         }
     }
 
+    /// Same as [`Strain::new`] but additionally tracks the strain peaks of
+    /// each column's individual strain.
+    pub fn with_column_peaks(total_columns: usize) -> Self {
+        Self {
+            column_peaks: Some(ColumnStrainPeaks::new(total_columns)),
+            ..Self::new(total_columns)
+        }
+    }
+
     pub fn get_curr_strain_peaks(self) -> StrainsVec {
         self.inner.get_curr_strain_peaks()
     }
 
+    /// The strain peaks of each column's individual strain.
+    ///
+    /// The outer list is indexed by column, the inner by section. Empty
+    /// unless created through [`Strain::with_column_peaks`].
+    pub fn get_curr_column_strain_peaks(self) -> Vec<Vec<f64>> {
+        self.column_peaks
+            .map_or_else(Vec::new, ColumnStrainPeaks::into_peaks)
+    }
+
     pub fn difficulty_value(self) -> f64 {
         Self::static_difficulty_value(self.inner)
     }
@@ -63,6 +84,12 @@ impl Strain {
         &mut self.inner.curr_strain
     }
 
+    fn save_column_peaks(&mut self, offset: f64) {
+        if let Some(ref mut column_peaks) = self.column_peaks {
+            column_peaks.save(offset, &self.individual_strains, &self.start_times);
+        }
+    }
+
     fn strain_value_at(&mut self, curr: &ManiaDifficultyObject) -> f64 {
         *self.curr_strain_mut() *= strain_decay(curr.delta_time, STRAIN_DECAY_BASE);
         *self.curr_strain_mut() += self.strain_value_of(curr) * SKILL_MULTIPLIER;
@@ -119,6 +146,10 @@ impl Strain {
         );
         self.individual_strains[column] += 2.0 * hold_factor;
 
+        if let Some(ref mut column_peaks) = self.column_peaks {
+            column_peaks.update(column, self.individual_strains[column]);
+        }
+
         // * For notes at the same time (in a chord), the individualStrain should be the hardest individualStrain out of those columns
         self.individual_strain = if mania_curr.delta_time <= 1.0 {
             self.individual_strain.max(self.individual_strains[column])
@@ -181,6 +212,7 @@ impl Skill<'_, Strain> {
 
         while curr.start_time > self.curr_section_end() {
             self.inner.inner.save_curr_peak();
+            self.inner.save_column_peaks(self.curr_section_end());
             let initial_strain = self.calculate_initial_strain(self.curr_section_end(), curr);
             self.inner.inner.start_new_section_from(initial_strain);
             *self.curr_section_end_mut() += StrainDecaySkill::SECTION_LEN;
@@ -191,6 +223,48 @@ impl Skill<'_, Strain> {
     }
 }
 
+/// Section peaks of each column's individual strain.
+struct ColumnStrainPeaks {
+    section_peaks: Box<[f64]>,
+    strain_peaks: Box<[Vec<f64>]>,
+}
+
+impl ColumnStrainPeaks {
+    fn new(total_columns: usize) -> Self {
+        Self {
+            section_peaks: vec![0.0; total_columns].into_boxed_slice(),
+            strain_peaks: vec![Vec::new(); total_columns].into_boxed_slice(),
+        }
+    }
+
+    fn update(&mut self, column: usize, individual_strain: f64) {
+        self.section_peaks[column] = self.section_peaks[column].max(individual_strain);
+    }
+
+    fn save(&mut self, offset: f64, individual_strains: &[f64], start_times: &[f64]) {
+        let columns = self
+            .section_peaks
+            .iter_mut()
+            .zip(self.strain_peaks.iter_mut())
+            .zip(individual_strains.iter().zip(start_times));
+
+        for ((peak, peaks), (strain, start_time)) in columns {
+            peaks.push(*peak);
+            *peak = apply_decay(*strain, offset - start_time, INDIVIDUAL_DECAY_BASE);
+        }
+    }
+
+    fn into_peaks(self) -> Vec<Vec<f64>> {
+        let mut strain_peaks = self.strain_peaks.into_vec();
+
+        for (peaks, peak) in strain_peaks.iter_mut().zip(self.section_peaks.iter()) {
+            peaks.push(*peak);
+        }
+
+        strain_peaks
+    }
+}
+
 fn apply_decay(value: f64, delta_time: f64, decay_base: f64) -> f64 {
     value * decay_base.powf(delta_time / 1000.0)
 }
@@ -8,17 +8,24 @@ use strain::Strain;
 use crate::util::mods::Mods;
 
 const SECTION_LEN: f32 = 400.0;
-const STAR_SCALING_FACTOR: f32 = 0.018;
+/// Converts the strain's difficulty value into the ppv1 mania star rating.
+pub const STAR_SCALING_FACTOR: f64 = 0.018;
 
 /// Star calculation for osu!mania maps
-pub fn stars(map: &Beatmap, mods: u32) -> ManiaDifficultyAttributes {
+///
+/// If no clock rate is specified, or it is `NaN` or infinite, it will be
+/// derived from the mods.
+pub fn stars(map: &Beatmap, mods: u32, clock_rate: Option<f64>) -> ManiaDifficultyAttributes {
     if map.hit_objects.len() < 2 {
         return ManiaDifficultyAttributes::default();
     }
 
     let columns = map.cs.round().max(1.0) as u8;
 
-    let clock_rate = mods.clock_rate() as f32;
+    let clock_rate = clock_rate
+        .filter(|rate| rate.is_finite())
+        .map_or_else(|| mods.clock_rate(), |rate| rate.clamp(0.01, 100.0))
+        as f32;
     let section_len = SECTION_LEN * clock_rate;
     let mut strain = Strain::new(columns);
 
@@ -56,7 +63,7 @@ pub fn stars(map: &Beatmap, mods: u32) -> ManiaDifficultyAttributes {
 
     strain.save_current_peak();
 
-    let stars = (strain.difficulty_value() * STAR_SCALING_FACTOR) as f64;
+    let stars = (strain.difficulty_value() * STAR_SCALING_FACTOR as f32) as f64;
 
     ManiaDifficultyAttributes { stars }
 }
@@ -83,10 +90,12 @@ impl<'o> DifficultyHitObject<'o> {
 }
 
 #[derive(Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ManiaDifficultyAttributes {
     pub stars: f64,
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ManiaPerformanceAttributes {
     pub difficulty: ManiaDifficultyAttributes,
     pub pp: f64,
@@ -7,6 +7,10 @@ use crate::util::mods::Mods;
 
 use super::{stars, ManiaDifficultyAttributes, ManiaPerformanceAttributes};
 
+/// Multiplier on the combined strain and accuracy pp of mania ppv1, which NF
+/// and EZ lower to 90% and 50%.
+pub const PERFORMANCE_BASE_MULTIPLIER: f64 = 1.1;
+
 /// Calculator for pp on osu!mania maps.
 ///
 /// # Example
@@ -39,6 +43,7 @@ pub struct ManiaPP<'m> {
     mods: u32,
     score: Option<f32>,
     acc: f32,
+    clock_rate: Option<f64>,
 }
 
 impl<'m> ManiaPP<'m> {
@@ -50,6 +55,7 @@ impl<'m> ManiaPP<'m> {
             mods: 0,
             score: None,
             acc: 1.0,
+            clock_rate: None,
         }
     }
 
@@ -79,6 +85,8 @@ impl<'m> ManiaPP<'m> {
 
     /// Specify the score of a play.
     /// On `NoMod` its between 0 and 1,000,000, on `Easy` between 0 and 500,000, etc.
+    ///
+    /// If no score is specified, it will be estimated through the accuracy.
     #[inline]
     pub fn score(mut self, score: u32) -> Self {
         self.score.replace(score as f32);
@@ -87,6 +95,12 @@ impl<'m> ManiaPP<'m> {
     }
 
     /// Specify the accuracy of a play between 0.0 and 100.0.
+    ///
+    /// If no score is specified, the accuracy is used to estimate the score
+    /// as `1,000,000 * accuracy`, halved for each of NF, EZ, and HT just like
+    /// a specified score. Both the base score and the bonus score of scorev1
+    /// make up half of the total and scale roughly linearly with accuracy, so
+    /// this serves as a decent approximation.
     #[inline]
     pub fn accuracy(mut self, acc: f32) -> Self {
         self.acc = acc / 100.0;
@@ -94,21 +108,47 @@ impl<'m> ManiaPP<'m> {
         self
     }
 
+    /// Adjust the clock rate used in the calculation.
+    ///
+    /// If none is specified, it will take the clock rate based on the mods
+    /// i.e. 1.5 for DT, 0.75 for HT and 1.0 otherwise.
+    ///
+    /// | Minimum | Maximum |
+    /// | :-----: | :-----: |
+    /// | 0.01    | 100     |
+    ///
+    /// A `NaN` or infinite value is ignored, i.e. the clock rate based on the
+    /// mods is used.
+    #[inline]
+    pub fn clock_rate(mut self, clock_rate: f64) -> Self {
+        self.clock_rate = if clock_rate.is_finite() {
+            Some(clock_rate.clamp(0.01, 100.0))
+        } else {
+            None
+        };
+
+        self
+    }
+
     /// Returns an object which contains the pp and stars.
     pub fn calculate(self) -> ManiaPerformanceAttributes {
         let stars = self
             .stars
-            .unwrap_or_else(|| stars(self.map, self.mods).stars as f32);
+            .unwrap_or_else(|| stars(self.map, self.mods, self.clock_rate).stars as f32);
 
         let ez = self.mods.ez();
         let nf = self.mods.nf();
         let ht = self.mods.ht();
 
-        let scaled_score = self.score.map_or(1_000_000.0, |score| {
-            score / 0.5_f32.powi(ez as i32 + nf as i32 + ht as i32)
-        });
+        let score_multiplier = 0.5_f32.powi(ez as i32 + nf as i32 + ht as i32);
+
+        let score = self
+            .score
+            .unwrap_or(1_000_000.0 * self.acc * score_multiplier);
+
+        let scaled_score = score / score_multiplier;
 
-        let mut multiplier = 1.1;
+        let mut multiplier = PERFORMANCE_BASE_MULTIPLIER as f32;
 
         if nf {
             multiplier *= 0.9;
@@ -127,7 +167,7 @@ impl<'m> ManiaPP<'m> {
                 od /= 1.4;
             }
 
-            let clock_rate = self.mods.clock_rate();
+            let clock_rate = self.clock_rate.unwrap_or_else(|| self.mods.clock_rate());
 
             ((od * clock_rate as f32).floor() / clock_rate as f32).ceil()
         };
@@ -227,3 +267,102 @@ impl ManiaAttributeProvider for PerformanceAttributes {
         self.difficulty_attributes().attributes()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn map() -> Beatmap {
+        let hit_objects: String = (0..60)
+            .map(|i| {
+                format!(
+                    "{},192,{},1,0,0:0:0:0:\n",
+                    64 + (i % 4) * 128,
+                    1000 + i * 120
+                )
+            })
+            .collect();
+
+        let content = format!(
+            "osu file format v14
+
+[General]
+Mode: 3
+
+[Difficulty]
+HPDrainRate:5
+CircleSize:4
+OverallDifficulty:8
+ApproachRate:5
+SliderMultiplier:1.4
+SliderTickRate:1
+
+[TimingPoints]
+0,300,4,2,0,100,1,0
+
+[HitObjects]
+{hit_objects}"
+        );
+
+        Beatmap::from_bytes(content.as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn accuracy_matches_equivalent_score() {
+        let map = Beatmap::default();
+
+        // NM, NF, EZ, HT, NFEZ, EZHT
+        for (mods, score) in [
+            (0, 980_000),
+            (1, 490_000),
+            (2, 490_000),
+            (256, 490_000),
+            (3, 245_000),
+            (258, 245_000),
+        ] {
+            let calc = ManiaPP::new(&map)
+                .attributes(5.0_f32)
+                .mods(mods)
+                .accuracy(98.0);
+
+            let acc_pp = calc.clone().calculate().pp;
+            let score_pp = calc.score(score).calculate().pp;
+
+            assert!(
+                (acc_pp - score_pp).abs() < 0.01,
+                "mods={mods}: {acc_pp} vs {score_pp}"
+            );
+        }
+    }
+
+    #[test]
+    fn clock_rate_matches_mods() {
+        let map = map();
+
+        let dt = ManiaPP::new(&map).mods(64).accuracy(97.0).calculate();
+
+        let rate = ManiaPP::new(&map)
+            .clock_rate(1.5)
+            .accuracy(97.0)
+            .calculate();
+
+        assert_eq!(dt.difficulty.stars, rate.difficulty.stars);
+        assert_eq!(dt.pp, rate.pp);
+
+        let nm = ManiaPP::new(&map).accuracy(97.0).calculate();
+        assert!(nm.difficulty.stars < dt.difficulty.stars);
+
+        for clock_rate in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            let ignored = ManiaPP::new(&map)
+                .clock_rate(clock_rate)
+                .accuracy(97.0)
+                .calculate();
+
+            assert_eq!(ignored.difficulty.stars, nm.difficulty.stars);
+            assert_eq!(ignored.pp, nm.pp);
+
+            let stars = stars(&map, 64, Some(clock_rate)).stars;
+            assert_eq!(stars, dt.difficulty.stars);
+        }
+    }
+}
@@ -4,6 +4,10 @@ use crate::util::mods::Mods;
 
 use super::stars::{stars, OsuDifficultyAttributes, OsuPerformanceAttributes};
 
+/// Factor by which the combined aim, speed, and accuracy pp are scaled in the
+/// May 2014 algorithm.
+pub const PERFORMANCE_BASE_MULTIPLIER: f64 = 1.1;
+
 /// Calculator for pp on osu!standard maps.
 ///
 /// # Example
@@ -231,7 +235,7 @@ impl<'m> OsuPP<'m> {
         self.assert_hitresults();
 
         let total_hits = self.total_hits() as f32;
-        let mut multiplier = 1.1;
+        let mut multiplier = PERFORMANCE_BASE_MULTIPLIER as f32;
 
         // NF penalty
         if self.mods.nf() {
@@ -7,7 +7,9 @@ use rosu_pp::Beatmap;
 
 const OBJECT_RADIUS: f32 = 64.0;
 const SECTION_LEN: f32 = 400.0;
-const DIFFICULTY_MULTIPLIER: f32 = 0.045;
+/// Scales the square root of the aim and speed difficulty values into their
+/// ratings; later versions use `0.0675`.
+pub const DIFFICULTY_MULTIPLIER: f64 = 0.045;
 const NORMALIZED_RADIUS: f32 = 52.0;
 
 // Old versions had different hit windows
@@ -113,8 +115,8 @@ pub fn stars(map: &Beatmap, mods: u32) -> OsuDifficultyAttributes {
     aim.save_current_peak();
     speed.save_current_peak();
 
-    let aim_strain = aim.difficulty_value().sqrt() * DIFFICULTY_MULTIPLIER;
-    let speed_strain = speed.difficulty_value().sqrt() * DIFFICULTY_MULTIPLIER;
+    let aim_strain = aim.difficulty_value().sqrt() * DIFFICULTY_MULTIPLIER as f32;
+    let speed_strain = speed.difficulty_value().sqrt() * DIFFICULTY_MULTIPLIER as f32;
 
     let stars = aim_strain + speed_strain + (aim_strain - speed_strain).abs() / 2.0;
 
@@ -126,6 +128,7 @@ pub fn stars(map: &Beatmap, mods: u32) -> OsuDifficultyAttributes {
 }
 
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OsuDifficultyAttributes {
     pub aim_strain: f64,
     pub speed_strain: f64,
@@ -139,7 +142,16 @@ pub struct OsuDifficultyAttributes {
     pub max_combo: usize,
 }
 
+impl OsuDifficultyAttributes {
+    /// Return the maximum combo.
+    #[inline]
+    pub fn max_combo(&self) -> usize {
+        self.max_combo
+    }
+}
+
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OsuPerformanceAttributes {
     pub difficulty: OsuDifficultyAttributes,
     pub pp: f64,
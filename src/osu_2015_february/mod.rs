@@ -5,7 +5,7 @@ mod osu_object;
 use osu_object::OsuObject;
 
 mod pp;
-pub use pp::{OsuAttributeProvider, OsuPP};
+pub use pp::{OsuAttributeProvider, OsuPP, PERFORMANCE_BASE_MULTIPLIER};
 
 mod skill;
 use skill::Skill;
@@ -14,3 +14,5 @@ mod skill_kind;
 use skill_kind::SkillKind;
 
 mod stars;
+pub(crate) use stars::stars;
+pub use stars::DIFFICULTY_MULTIPLIER;
@@ -4,6 +4,10 @@ use crate::util::mods::Mods;
 
 use super::stars::{stars, OsuDifficultyAttributes, OsuPerformanceAttributes};
 
+/// Raised from `1.1` in this version; scales the combined aim, speed, and
+/// accuracy pp.
+pub const PERFORMANCE_BASE_MULTIPLIER: f64 = 1.12;
+
 /// Calculator for pp on osu!standard maps.
 ///
 /// # Example
@@ -231,7 +235,7 @@ impl<'m> OsuPP<'m> {
         self.assert_hitresults();
 
         let total_hits = self.total_hits() as f32;
-        let mut multiplier = 1.12;
+        let mut multiplier = PERFORMANCE_BASE_MULTIPLIER as f32;
 
         // NF penalty
         if self.mods.nf() {
@@ -1,9 +1,12 @@
-use rosu_pp::Beatmap;
+use rosu_pp::{osu::OsuScoreState, Beatmap};
 
 use crate::util::mods::Mods;
 
 use super::stars::{stars, OsuDifficultyAttributes, OsuPerformanceAttributes};
 
+/// Scale of the final pp in the 2019 algorithm. NF and SO reduce it further.
+pub const PERFORMANCE_BASE_MULTIPLIER: f64 = 1.12;
+
 /// Calculator for pp on osu!standard maps.
 ///
 /// # Example
@@ -42,6 +45,7 @@ pub struct OsuPP<'m> {
     n100: Option<u32>,
     n50: Option<u32>,
     n_misses: u32,
+    passed_objects: Option<usize>,
 }
 
 impl<'m> OsuPP<'m> {
@@ -57,6 +61,7 @@ impl<'m> OsuPP<'m> {
             n100: None,
             n50: None,
             n_misses: 0,
+            passed_objects: None,
         }
     }
 
@@ -124,11 +129,46 @@ impl<'m> OsuPP<'m> {
         self
     }
 
+    /// Amount of passed objects for partial plays, e.g. a fail.
+    ///
+    /// Be sure to set this before [`accuracy`](Self::accuracy).
+    #[inline]
+    pub fn passed_objects(mut self, passed_objects: usize) -> Self {
+        self.passed_objects.replace(passed_objects);
+
+        self
+    }
+
+    /// Provide parameters through an [`OsuScoreState`].
+    ///
+    /// Slider tick and slider end hits are ignored since they were not
+    /// considered at the time.
+    #[inline]
+    pub fn state(mut self, state: OsuScoreState) -> Self {
+        let OsuScoreState {
+            max_combo,
+            n300,
+            n100,
+            n50,
+            misses,
+            ..
+        } = state;
+
+        self.combo = Some(max_combo);
+        self.n300 = Some(n300);
+        self.n100 = Some(n100);
+        self.n50 = Some(n50);
+        self.n_misses = misses;
+        self.acc = None;
+
+        self
+    }
+
     /// Generate the hit results with respect to the given accuracy between `0` and `100`.
     ///
     /// Be sure to set `misses` beforehand!
     pub fn accuracy(mut self, acc: f32) -> Self {
-        let n_objects = self.map.hit_objects.len() as u32;
+        let n_objects = self.n_objects();
 
         let acc = acc / 100.0;
 
@@ -187,7 +227,7 @@ impl<'m> OsuPP<'m> {
 
     fn assert_hitresults(&mut self) {
         if self.acc.is_none() {
-            let n_objects = self.map.hit_objects.len() as u32;
+            let n_objects = self.n_objects();
 
             let remaining = n_objects
                 .saturating_sub(self.n300.unwrap_or(0))
@@ -223,7 +263,7 @@ impl<'m> OsuPP<'m> {
     /// containing stars and other attributes.
     pub fn calculate(mut self) -> OsuPerformanceAttributes {
         if self.attributes.is_none() {
-            let attributes = stars(self.map, self.mods);
+            let attributes = stars(self.map, self.mods, self.passed_objects);
             self.attributes.replace(attributes);
         }
 
@@ -231,7 +271,7 @@ impl<'m> OsuPP<'m> {
         self.assert_hitresults();
 
         let total_hits = self.total_hits() as f32;
-        let mut multiplier = 1.12;
+        let mut multiplier = PERFORMANCE_BASE_MULTIPLIER as f32;
 
         // NF penalty
         if self.mods.nf() {
@@ -385,8 +425,14 @@ impl<'m> OsuPP<'m> {
         acc_value
     }
 
+    fn n_objects(&self) -> u32 {
+        let n_objects = self.map.hit_objects.len();
+
+        self.passed_objects.map_or(n_objects, |n| n.min(n_objects)) as u32
+    }
+
     fn total_hits(&self) -> u32 {
-        let n_objects = self.map.hit_objects.len() as u32;
+        let n_objects = self.n_objects();
 
         (self.n300.unwrap_or(0) + self.n100.unwrap_or(0) + self.n50.unwrap_or(0) + self.n_misses)
             .min(n_objects)
@@ -410,3 +456,63 @@ impl OsuAttributeProvider for OsuPerformanceAttributes {
         Some(self.difficulty)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn map() -> Beatmap {
+        let hit_objects: String = (0..40)
+            .map(|i| {
+                let x = if i % 2 == 0 { 64 } else { 448 };
+
+                format!(
+                    "{x},{},{},1,0,0:0:0:0:\n",
+                    64 + (i % 5) * 64,
+                    1000 + i * 150
+                )
+            })
+            .collect();
+
+        let content = format!(
+            "osu file format v14
+
+[General]
+Mode: 0
+
+[Difficulty]
+HPDrainRate:5
+CircleSize:4
+OverallDifficulty:8
+ApproachRate:9
+SliderMultiplier:1.4
+SliderTickRate:1
+
+[TimingPoints]
+0,300,4,2,0,100,1,0
+
+[HitObjects]
+{hit_objects}"
+        );
+
+        Beatmap::from_bytes(content.as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn passed_objects() {
+        let map = map();
+
+        let full = OsuPP::new(&map).calculate();
+        let all_passed = OsuPP::new(&map).passed_objects(40).calculate();
+
+        assert!(full.difficulty.stars > 0.0);
+        assert_eq!(all_passed.difficulty.stars, full.difficulty.stars);
+        assert_eq!(all_passed.pp, full.pp);
+
+        let partial = OsuPP::new(&map).passed_objects(10).calculate();
+
+        assert_eq!(partial.difficulty.n_circles, 10);
+        assert!(partial.difficulty.stars < full.difficulty.stars);
+        assert!(partial.pp < full.pp);
+    }
+}
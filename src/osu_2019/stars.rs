@@ -5,7 +5,9 @@ use rosu_pp::Beatmap;
 
 const OBJECT_RADIUS: f32 = 64.0;
 const SECTION_LEN: f32 = 400.0;
-const DIFFICULTY_MULTIPLIER: f32 = 0.0675;
+/// The aim and speed ratings of the 2019 algorithm are the square root of the
+/// respective difficulty value times this multiplier.
+pub const DIFFICULTY_MULTIPLIER: f64 = 0.0675;
 const NORMALIZED_RADIUS: f32 = 52.0;
 
 /// Star calculation for osu!standard maps.
@@ -15,8 +17,11 @@ const NORMALIZED_RADIUS: f32 = 52.0;
 /// it has generally little effect on stars, the results are close to perfect.
 /// This version is considerably more efficient than `all_included` since
 /// processing stack leniency is relatively expensive.
-pub fn stars(map: &Beatmap, mods: u32) -> OsuDifficultyAttributes {
+///
+/// If `passed_objects` is specified, only that many hit objects are considered.
+pub fn stars(map: &Beatmap, mods: u32, passed_objects: Option<usize>) -> OsuDifficultyAttributes {
     let map_attributes = map.attributes().mods(mods).build();
+    let take = passed_objects.unwrap_or(map.hit_objects.len());
 
     let mut diff_attributes = OsuDifficultyAttributes {
         ar: map_attributes.ar,
@@ -24,7 +29,7 @@ pub fn stars(map: &Beatmap, mods: u32) -> OsuDifficultyAttributes {
         ..Default::default()
     };
 
-    if map.hit_objects.len() < 2 {
+    if take < 2 || map.hit_objects.len() < 2 {
         return diff_attributes;
     }
 
@@ -40,7 +45,7 @@ pub fn stars(map: &Beatmap, mods: u32) -> OsuDifficultyAttributes {
     let mut ticks_buf = Vec::new();
     let mut curve_bufs = CurveBuffers::default();
 
-    let mut hit_objects = map.hit_objects.iter().map(|h| {
+    let mut hit_objects = map.hit_objects.iter().take(take).map(|h| {
         OsuObject::new(
             h,
             map,
@@ -116,8 +121,8 @@ pub fn stars(map: &Beatmap, mods: u32) -> OsuDifficultyAttributes {
     aim.save_current_peak();
     speed.save_current_peak();
 
-    let aim_strain = aim.difficulty_value().sqrt() * DIFFICULTY_MULTIPLIER;
-    let speed_strain = speed.difficulty_value().sqrt() * DIFFICULTY_MULTIPLIER;
+    let aim_strain = aim.difficulty_value().sqrt() * DIFFICULTY_MULTIPLIER as f32;
+    let speed_strain = speed.difficulty_value().sqrt() * DIFFICULTY_MULTIPLIER as f32;
 
     let stars = aim_strain + speed_strain + (aim_strain - speed_strain).abs() / 2.0;
 
@@ -129,6 +134,7 @@ pub fn stars(map: &Beatmap, mods: u32) -> OsuDifficultyAttributes {
 }
 
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OsuDifficultyAttributes {
     pub aim_strain: f64,
     pub speed_strain: f64,
@@ -142,6 +148,21 @@ pub struct OsuDifficultyAttributes {
     pub max_combo: usize,
 }
 
+impl OsuDifficultyAttributes {
+    /// Return the maximum combo.
+    #[inline]
+    pub fn max_combo(&self) -> usize {
+        self.max_combo
+    }
+
+    /// Return the amount of hitobjects.
+    #[inline]
+    pub fn n_objects(&self) -> usize {
+        self.n_circles + self.n_sliders + self.n_spinners
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OsuPerformanceAttributes {
     pub difficulty: OsuDifficultyAttributes,
     pub pp: f64,
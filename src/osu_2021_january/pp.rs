@@ -4,6 +4,10 @@ use crate::util::mods::Mods;
 
 use super::stars::{stars, OsuDifficultyAttributes, OsuPerformanceAttributes};
 
+/// Scales the combined pp of the January 2021 rework. NF lowers it by 2% per
+/// miss down to 90% and SO by the share of spinners.
+pub const PERFORMANCE_BASE_MULTIPLIER: f64 = 1.12;
+
 /// Calculator for pp on osu!standard maps.
 ///
 /// # Example
@@ -229,7 +233,7 @@ impl<'m> OsuPP<'m> {
         self.assert_hitresults();
 
         let total_hits = self.total_hits() as f32;
-        let mut multiplier = 1.12;
+        let mut multiplier = PERFORMANCE_BASE_MULTIPLIER as f32;
 
         // NF penalty
         if self.mods.nf() {
@@ -6,7 +6,7 @@ mod skill_kind;
 
 use difficulty_object::DifficultyObject;
 use osu_object::OsuObject;
-pub use pp::{OsuAttributeProvider, OsuPP};
+pub use pp::{OsuAttributeProvider, OsuPP, PERFORMANCE_BASE_MULTIPLIER};
 use rosu_map::section::hit_objects::CurveBuffers;
 use rosu_pp::{model::hit_object::HitObjectKind, Beatmap};
 use skill::Skill;
@@ -14,7 +14,9 @@ use skill_kind::SkillKind;
 
 const OBJECT_RADIUS: f32 = 64.0;
 const SECTION_LEN: f32 = 400.0;
-const DIFFICULTY_MULTIPLIER: f32 = 0.0675;
+/// In the July 2021 version, the square roots of the aim and speed difficulty
+/// values are scaled by this to get their ratings.
+pub const DIFFICULTY_MULTIPLIER: f64 = 0.0675;
 const NORMALIZED_RADIUS: f32 = 52.0;
 
 /// Star calculation for osu!standard maps.
@@ -123,8 +125,8 @@ pub fn stars(map: &Beatmap, mods: u32) -> OsuDifficultyAttributes {
     aim.save_current_peak();
     speed.save_current_peak();
 
-    let aim_rating = aim.difficulty_value().sqrt() * DIFFICULTY_MULTIPLIER;
-    let speed_rating = speed.difficulty_value().sqrt() * DIFFICULTY_MULTIPLIER;
+    let aim_rating = aim.difficulty_value().sqrt() * DIFFICULTY_MULTIPLIER as f32;
+    let speed_rating = speed.difficulty_value().sqrt() * DIFFICULTY_MULTIPLIER as f32;
 
     let stars = aim_rating + speed_rating + (aim_rating - speed_rating).abs() / 2.0;
 
@@ -139,6 +141,7 @@ pub fn stars(map: &Beatmap, mods: u32) -> OsuDifficultyAttributes {
 }
 
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OsuDifficultyAttributes {
     pub aim_strain: f64,
     pub speed_strain: f64,
@@ -152,6 +155,33 @@ pub struct OsuDifficultyAttributes {
     pub max_combo: usize,
 }
 
+impl OsuDifficultyAttributes {
+    /// Return the maximum combo.
+    #[inline]
+    pub fn max_combo(&self) -> usize {
+        self.max_combo
+    }
+}
+
+/// Both versions have the same fields so all of them are copied over as is.
+impl From<crate::osu_2019::OsuDifficultyAttributes> for OsuDifficultyAttributes {
+    fn from(attributes: crate::osu_2019::OsuDifficultyAttributes) -> Self {
+        Self {
+            aim_strain: attributes.aim_strain,
+            speed_strain: attributes.speed_strain,
+            ar: attributes.ar,
+            od: attributes.od,
+            hp: attributes.hp,
+            n_circles: attributes.n_circles,
+            n_sliders: attributes.n_sliders,
+            n_spinners: attributes.n_spinners,
+            stars: attributes.stars,
+            max_combo: attributes.max_combo,
+        }
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OsuPerformanceAttributes {
     pub difficulty: OsuDifficultyAttributes,
     pub pp: f64,
@@ -168,3 +198,37 @@ impl OsuPerformanceAttributes {
         self.difficulty.max_combo
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_2019_attributes() {
+        let attrs = crate::osu_2019::OsuDifficultyAttributes {
+            aim_strain: 2.75,
+            speed_strain: 2.5,
+            ar: 9.5,
+            od: 8.25,
+            hp: 6.0,
+            n_circles: 400,
+            n_sliders: 150,
+            n_spinners: 2,
+            stars: 5.5,
+            max_combo: 900,
+        };
+
+        let converted = OsuDifficultyAttributes::from(attrs.clone());
+
+        assert_eq!(converted.aim_strain, attrs.aim_strain);
+        assert_eq!(converted.speed_strain, attrs.speed_strain);
+        assert_eq!(converted.ar, attrs.ar);
+        assert_eq!(converted.od, attrs.od);
+        assert_eq!(converted.hp, attrs.hp);
+        assert_eq!(converted.n_circles, attrs.n_circles);
+        assert_eq!(converted.n_sliders, attrs.n_sliders);
+        assert_eq!(converted.n_spinners, attrs.n_spinners);
+        assert_eq!(converted.stars, attrs.stars);
+        assert_eq!(converted.max_combo, attrs.max_combo);
+    }
+}
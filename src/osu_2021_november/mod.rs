@@ -21,7 +21,9 @@ use crate::util::mods::Mods;
 use self::skill::Skills;
 
 const SECTION_LEN: f64 = 400.0;
-const DIFFICULTY_MULTIPLIER: f64 = 0.0675;
+/// Scales the square root of the aim, speed, and flashlight difficulty values
+/// into their ratings.
+pub const DIFFICULTY_MULTIPLIER: f64 = 0.0675;
 const NORMALIZED_RADIUS: f32 = 50.0; // * diameter of 100; easier mental maths.
 const STACK_DISTANCE: f32 = 3.0;
 
@@ -351,6 +353,7 @@ fn old_stacking(hit_objects: &mut [OsuObject], stack_threshold: f64) {
 
 /// The result of a difficulty calculation on an osu!standard map.
 #[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OsuDifficultyAttributes {
     /// The aim portion of the total strain.
     pub aim_strain: f64,
@@ -386,8 +389,32 @@ impl OsuDifficultyAttributes {
     }
 }
 
+/// Shared fields are copied over as is.
+///
+/// `flashlight_rating` is set to `0.0` and `slider_factor` to `1.0`, i.e. no
+/// slider nerf, because july 2021 did not calculate them.
+impl From<crate::osu_2021_july::OsuDifficultyAttributes> for OsuDifficultyAttributes {
+    fn from(attributes: crate::osu_2021_july::OsuDifficultyAttributes) -> Self {
+        Self {
+            aim_strain: attributes.aim_strain,
+            speed_strain: attributes.speed_strain,
+            flashlight_rating: 0.0,
+            slider_factor: 1.0,
+            ar: attributes.ar,
+            od: attributes.od,
+            hp: attributes.hp,
+            n_circles: attributes.n_circles,
+            n_sliders: attributes.n_sliders,
+            n_spinners: attributes.n_spinners,
+            stars: attributes.stars,
+            max_combo: attributes.max_combo as u32,
+        }
+    }
+}
+
 /// The result of a performance calculation on an osu!standard map.
 #[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OsuPerformanceAttributes {
     /// The difficulty attributes that were used for the performance calculation
     pub difficulty: OsuDifficultyAttributes,
@@ -438,3 +465,41 @@ fn _difficulty_range(val: f64, max: f64, avg: f64, min: f64) -> f64 {
         avg
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_july_2021_attributes() {
+        let attrs = crate::osu_2021_july::OsuDifficultyAttributes {
+            aim_strain: 2.75,
+            speed_strain: 2.5,
+            ar: 9.5,
+            od: 8.25,
+            hp: 6.0,
+            n_circles: 400,
+            n_sliders: 150,
+            n_spinners: 2,
+            stars: 5.5,
+            max_combo: 900,
+        };
+
+        let converted = OsuDifficultyAttributes::from(attrs.clone());
+
+        assert_eq!(converted.aim_strain, attrs.aim_strain);
+        assert_eq!(converted.speed_strain, attrs.speed_strain);
+        assert_eq!(converted.ar, attrs.ar);
+        assert_eq!(converted.od, attrs.od);
+        assert_eq!(converted.hp, attrs.hp);
+        assert_eq!(converted.n_circles, attrs.n_circles);
+        assert_eq!(converted.n_sliders, attrs.n_sliders);
+        assert_eq!(converted.n_spinners, attrs.n_spinners);
+        assert_eq!(converted.stars, attrs.stars);
+        assert_eq!(converted.max_combo, 900);
+
+        // Not calculated in july 2021
+        assert_eq!(converted.flashlight_rating, 0.0);
+        assert_eq!(converted.slider_factor, 1.0);
+    }
+}
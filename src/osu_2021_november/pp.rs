@@ -4,6 +4,11 @@ use crate::util::mods::Mods;
 
 use super::{OsuDifficultyAttributes, OsuPerformanceAttributes};
 
+/// Multiplier of the November 2021 version, applied after combining aim,
+/// speed, accuracy, and flashlight pp. The NF penalty is based on the
+/// effective miss count.
+pub const PERFORMANCE_BASE_MULTIPLIER: f64 = 1.12;
+
 /// Performance calculator on osu!standard maps.
 ///
 /// # Example
@@ -320,7 +325,7 @@ struct OsuPPInner {
 
 impl OsuPPInner {
     fn calculate(mut self) -> OsuPerformanceAttributes {
-        let mut multiplier = 1.12;
+        let mut multiplier = PERFORMANCE_BASE_MULTIPLIER;
 
         // NF penalty
         if self.mods.nf() {
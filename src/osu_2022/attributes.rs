@@ -1,5 +1,8 @@
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
 /// The result of a difficulty calculation on an osu!standard map.
 #[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OsuDifficultyAttributes {
     /// The difficulty of the aim skill.
     pub aim: f64,
@@ -9,11 +12,32 @@ pub struct OsuDifficultyAttributes {
     pub flashlight: f64,
     /// The ratio of the aim strain with and without considering sliders
     pub slider_factor: f64,
-    /// The number of clickable objects weighted by difficulty.
+    /// The number of clickable objects weighted by difficulty, i.e. the
+    /// number of notes relevant to speed.
     pub speed_note_count: f64,
+    /// Weighted amount of aim strains that are close to the top strain.
+    ///
+    /// Note that this was not part of the difficulty calculation at the time
+    /// and is approximated with a formula of a later osu!lazer version.
+    pub aim_difficult_strain_count: f64,
+    /// Weighted amount of speed strains that are close to the top strain.
+    ///
+    /// Note that this was not part of the difficulty calculation at the time
+    /// and is approximated with a formula of a later osu!lazer version.
+    pub speed_difficult_strain_count: f64,
     /// The approach rate.
+    ///
+    /// The value is adjusted by the mods, including the clock rate of mods
+    /// such as DT or HT, i.e. it is the approach rate as perceived during
+    /// gameplay. Note that a custom clock rate specified through
+    /// [`OsuStars::clock_rate`] is not considered.
+    ///
+    /// [`OsuStars::clock_rate`]: crate::osu_2022::OsuStars::clock_rate
     pub ar: f64,
     /// The overall difficulty
+    ///
+    /// Just like [`ar`](Self::ar), the value is adjusted by the mods,
+    /// including their clock rate.
     pub od: f64,
     /// The health drain rate.
     pub hp: f64,
@@ -23,6 +47,8 @@ pub struct OsuDifficultyAttributes {
     pub n_sliders: u32,
     /// The amount of spinners.
     pub n_spinners: u32,
+    /// The amount of slider ticks and repeat points.
+    pub n_large_ticks: u32,
     /// The final star rating
     pub stars: f64,
     /// The maximum combo.
@@ -41,8 +67,44 @@ impl OsuDifficultyAttributes {
     }
 }
 
+/// Shared fields are copied over as is with `aim_strain`, `speed_strain`, and
+/// `flashlight_rating` becoming `aim`, `speed`, and `flashlight`.
+///
+/// November 2021 did not calculate `speed_note_count` so it is estimated as
+/// the amount of circles and sliders, i.e. every note is considered relevant
+/// to speed. The relevant accuracy of a performance calculation is then close
+/// to the overall accuracy, just like november 2021 scaled speed by accuracy.
+/// Since the actual speed note count is usually lower, pp calculated from
+/// converted attributes are not directly comparable to pp calculated from
+/// attributes of this version.
+///
+/// The difficult strain counts and `n_large_ticks` are set to `0`.
+impl From<crate::osu_2021_november::OsuDifficultyAttributes> for OsuDifficultyAttributes {
+    fn from(attributes: crate::osu_2021_november::OsuDifficultyAttributes) -> Self {
+        Self {
+            aim: attributes.aim_strain,
+            speed: attributes.speed_strain,
+            flashlight: attributes.flashlight_rating,
+            slider_factor: attributes.slider_factor,
+            speed_note_count: (attributes.n_circles + attributes.n_sliders) as f64,
+            aim_difficult_strain_count: 0.0,
+            speed_difficult_strain_count: 0.0,
+            ar: attributes.ar,
+            od: attributes.od,
+            hp: attributes.hp,
+            n_circles: attributes.n_circles as u32,
+            n_sliders: attributes.n_sliders as u32,
+            n_spinners: attributes.n_spinners as u32,
+            n_large_ticks: 0,
+            stars: attributes.stars,
+            max_combo: attributes.max_combo,
+        }
+    }
+}
+
 /// The result of a performance calculation on an osu!standard map.
 #[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OsuPerformanceAttributes {
     /// The difficulty attributes that were used for the performance calculation
     pub difficulty: OsuDifficultyAttributes,
@@ -58,6 +120,11 @@ pub struct OsuPerformanceAttributes {
     pub pp_speed: f64,
     /// Misses including an approximated amount of slider breaks
     pub effective_miss_count: f64,
+    /// The accuracy on the notes that are relevant for speed, assuming the
+    /// worst case distribution of hitresults.
+    pub relevant_acc: f64,
+    /// The amount of hitresults of the score, i.e. `n300 + n100 + n50 + misses`.
+    pub total_hits: u32,
 }
 
 impl OsuPerformanceAttributes {
@@ -75,14 +142,206 @@ impl OsuPerformanceAttributes {
     pub const fn max_combo(&self) -> u32 {
         self.difficulty.max_combo
     }
+
+    /// Return the amount of hitresults of the score, i.e.
+    /// `n300 + n100 + n50 + misses`.
+    ///
+    /// For partial plays, this is the amount of passed objects.
+    pub const fn total_hits(&self) -> u32 {
+        self.total_hits
+    }
+
+    /// Return the accuracy on the notes that are relevant for speed.
+    ///
+    /// See [`OsuDifficultyAttributes::speed_note_count`].
+    pub const fn relevant_acc(&self) -> f64 {
+        self.relevant_acc
+    }
+
     /// Return the amount of hitobjects.
     pub const fn n_objects(&self) -> u32 {
         self.difficulty.n_objects()
     }
 }
 
+/// The multipliers that were applied to the aim and speed portions of a
+/// performance calculation.
+///
+/// Returned by [`OsuPP::calculate_with_breakdown`].
+///
+/// [`OsuPP::calculate_with_breakdown`]: crate::osu_2022::OsuPP::calculate_with_breakdown
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OsuPerformanceBreakdown {
+    /// The breakdown of the aim portion.
+    pub aim: OsuSkillBreakdown,
+    /// The breakdown of the speed portion.
+    pub speed: OsuSkillBreakdown,
+}
+
+/// The base value and multipliers of a skill's portion of the pp.
+///
+/// Multipliers that do not apply to the skill or the score are `1.0`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OsuSkillBreakdown {
+    /// The value based only on the skill's difficulty.
+    pub base: f64,
+    /// Bonus for longer maps.
+    pub len_bonus: f64,
+    /// Penalty for the effective miss count.
+    pub miss_penalty: f64,
+    /// Scaling with the score's max combo.
+    pub combo_scaling: f64,
+    /// Bonus for high approach rates and, for aim, low ones.
+    pub ar_bonus: f64,
+    /// Bonus for the hidden mod.
+    pub hidden_bonus: f64,
+    /// Nerf for presumably dropped slider ends; aim only.
+    pub slider_nerf: f64,
+    /// Scaling with the score's accuracy.
+    pub accuracy: f64,
+    /// Scaling with the overall difficulty.
+    pub od: f64,
+    /// Penalty for 50s to punish doubletapping; speed only.
+    pub n50_penalty: f64,
+}
+
+impl OsuSkillBreakdown {
+    pub(crate) const fn new(base: f64) -> Self {
+        Self {
+            base,
+            len_bonus: 1.0,
+            miss_penalty: 1.0,
+            combo_scaling: 1.0,
+            ar_bonus: 1.0,
+            hidden_bonus: 1.0,
+            slider_nerf: 1.0,
+            accuracy: 1.0,
+            od: 1.0,
+            n50_penalty: 1.0,
+        }
+    }
+
+    /// The product of the base value and all multipliers.
+    ///
+    /// Up to floating point imprecision, this is the skill's portion of the
+    /// pp.
+    pub fn value(&self) -> f64 {
+        self.base
+            * self.len_bonus
+            * self.miss_penalty
+            * self.combo_scaling
+            * self.ar_bonus
+            * self.hidden_bonus
+            * self.slider_nerf
+            * self.accuracy
+            * self.od
+            * self.n50_penalty
+    }
+}
+
+impl Default for OsuSkillBreakdown {
+    fn default() -> Self {
+        Self::new(0.0)
+    }
+}
+
+/// Concise summary of the pp and its components, rounded to one decimal,
+/// e.g. `pp=523.4 (aim=280.1 speed=180.2 acc=50.3 fl=13.0)`.
+impl Display for OsuPerformanceAttributes {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(
+            f,
+            "pp={:.1} (aim={:.1} speed={:.1} acc={:.1} fl={:.1})",
+            self.pp, self.pp_aim, self.pp_speed, self.pp_acc, self.pp_flashlight
+        )
+    }
+}
+
 impl From<OsuPerformanceAttributes> for OsuDifficultyAttributes {
     fn from(attributes: OsuPerformanceAttributes) -> Self {
         attributes.difficulty
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_november_2021_attributes() {
+        let attrs = crate::osu_2021_november::OsuDifficultyAttributes {
+            aim_strain: 2.75,
+            speed_strain: 2.5,
+            flashlight_rating: 1.25,
+            slider_factor: 0.96875,
+            ar: 9.5,
+            od: 8.25,
+            hp: 6.0,
+            n_circles: 400,
+            n_sliders: 150,
+            n_spinners: 2,
+            stars: 5.5,
+            max_combo: 900,
+        };
+
+        let converted = OsuDifficultyAttributes::from(attrs.clone());
+
+        assert_eq!(converted.aim, attrs.aim_strain);
+        assert_eq!(converted.speed, attrs.speed_strain);
+        assert_eq!(converted.flashlight, attrs.flashlight_rating);
+        assert_eq!(converted.slider_factor, attrs.slider_factor);
+        assert_eq!(converted.ar, attrs.ar);
+        assert_eq!(converted.od, attrs.od);
+        assert_eq!(converted.hp, attrs.hp);
+        assert_eq!(converted.n_circles, 400);
+        assert_eq!(converted.n_sliders, 150);
+        assert_eq!(converted.n_spinners, 2);
+        assert_eq!(converted.stars, attrs.stars);
+        assert_eq!(converted.max_combo, attrs.max_combo);
+
+        // Estimated or not calculated in november 2021
+        assert_eq!(converted.speed_note_count, 550.0);
+        assert_eq!(converted.aim_difficult_strain_count, 0.0);
+        assert_eq!(converted.speed_difficult_strain_count, 0.0);
+        assert_eq!(converted.n_large_ticks, 0);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let attrs = OsuPerformanceAttributes {
+            difficulty: OsuDifficultyAttributes {
+                aim: 2.75,
+                speed: 2.5,
+                slider_factor: 0.96875,
+                speed_note_count: 312.5,
+                ar: 9.5,
+                od: 8.0,
+                n_circles: 400,
+                n_sliders: 150,
+                stars: 5.25,
+                max_combo: 900,
+                ..Default::default()
+            },
+            pp: 250.5,
+            pp_aim: 120.25,
+            pp_speed: 90.125,
+            effective_miss_count: 1.5,
+            ..Default::default()
+        };
+
+        let json = serde_json::to_string(&attrs).unwrap();
+        let deserialized: OsuPerformanceAttributes = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, attrs);
+
+        let mut breakdown = OsuPerformanceBreakdown::default();
+        breakdown.aim.len_bonus = 1.125;
+        breakdown.speed.miss_penalty = 0.875;
+
+        let json = serde_json::to_string(&breakdown).unwrap();
+        let deserialized: OsuPerformanceBreakdown = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, breakdown);
+    }
+}
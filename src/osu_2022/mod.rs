@@ -10,18 +10,26 @@ use difficulty_object::OsuDifficultyObject;
 use osu_object::OsuObject;
 use rosu_map::util::Pos;
 use rosu_pp::{
-    model::{beatmap::BeatmapAttributes, mode::GameMode},
+    model::{beatmap::BeatmapAttributes, mode::GameMode, mods::GameMods},
     Beatmap,
 };
 use scaling_factor::ScalingFactor;
 use skills::OsuSkills;
 
 pub use self::{
-    attributes::{OsuDifficultyAttributes, OsuPerformanceAttributes},
+    attributes::{
+        OsuDifficultyAttributes, OsuPerformanceAttributes, OsuPerformanceBreakdown,
+        OsuSkillBreakdown,
+    },
     pp::*,
+    strains::OsuStrains,
 };
 
-use crate::util::{mods::Mods, skills::Skill};
+use crate::util::{
+    convert::{convert_ref, ConvertError},
+    mods::{lazer_clock_rate, legacy_bits, Mods},
+    skills::Skill,
+};
 
 mod attributes;
 mod convert;
@@ -30,10 +38,13 @@ mod osu_object;
 mod pp;
 mod scaling_factor;
 mod skills;
+mod strains;
 
 const PLAYFIELD_BASE_SIZE: Pos = Pos::new(512.0, 384.0);
 
-const DIFFICULTY_MULTIPLIER: f64 = 0.0675;
+/// Scales the square root of the aim, speed, and flashlight difficulty values;
+/// the aim rating without sliders uses it too.
+pub const DIFFICULTY_MULTIPLIER: f64 = 0.0675;
 
 const HD_FADE_IN_DURATION_MULTIPLIER: f64 = 0.4;
 const HD_FADE_OUT_DURATION_MULTIPLIER: f64 = 0.3;
@@ -65,6 +76,14 @@ pub struct OsuStars {
     /// This allows for an optimization to reduce the struct size by storing its
     /// bits as a [`NonZeroU32`].
     clock_rate: Option<NonZeroU32>,
+    /// Clock rate of lazer mods, including custom speed changes.
+    ///
+    /// Only used if no clock rate was specified explicitly.
+    mods_clock_rate: Option<f32>,
+    ar: Option<f32>,
+    cs: Option<f32>,
+    hp: Option<f32>,
+    od: Option<f32>,
 }
 
 impl OsuStars {
@@ -74,14 +93,40 @@ impl OsuStars {
             mods: 0,
             passed_objects: None,
             clock_rate: None,
+            mods_clock_rate: None,
+            ar: None,
+            cs: None,
+            hp: None,
+            od: None,
         }
     }
 
     /// Specify mods.
     ///
+    /// Accepted types are
+    /// - `u32`
+    /// - [`rosu_mods::GameModsLegacy`]
+    /// - [`rosu_mods::GameMods`]
+    /// - [`rosu_mods::GameModsIntermode`]
+    /// - [`&rosu_mods::GameModsIntermode`](rosu_mods::GameModsIntermode)
+    ///
+    /// Mods are converted to their legacy bit values so lazer mods without
+    /// a legacy counterpart are ignored. The custom speed change of lazer
+    /// rate changing mods is kept and used unless a clock rate is specified
+    /// through [`clock_rate`](Self::clock_rate).
+    ///
+    /// This method used to be a `const fn` that only took `u32`; it can no
+    /// longer be used in const contexts.
+    ///
     /// See <https://github.com/ppy/osu-api/wiki#mods>
-    pub const fn mods(self, mods: u32) -> Self {
-        Self { mods, ..self }
+    pub fn mods(self, mods: impl Into<GameMods>) -> Self {
+        let mods = mods.into();
+
+        Self {
+            mods: legacy_bits(&mods),
+            mods_clock_rate: lazer_clock_rate(&mods).map(|clock_rate| clock_rate as f32),
+            ..self
+        }
     }
 
     /// Amount of passed objects for partial plays, e.g. a fail.
@@ -96,10 +141,24 @@ impl OsuStars {
     /// If none is specified, it will take the clock rate based on the mods
     /// i.e. 1.5 for DT, 0.75 for HT and 1.0 otherwise.
     ///
+    /// A specified clock rate always takes precedence over the mods, e.g.
+    /// DT with a clock rate of 0.9 uses 0.9. Use
+    /// [`reset_clock_rate`](Self::reset_clock_rate) to fall back to the mods.
+    ///
     /// | Minimum | Maximum |
     /// | :-----: | :-----: |
     /// | 0.01    | 100     |
+    ///
+    /// A `NaN` or infinite value is ignored, i.e. the clock rate based on the
+    /// mods is used.
     pub fn clock_rate(self, clock_rate: f64) -> Self {
+        if !clock_rate.is_finite() {
+            return Self {
+                clock_rate: None,
+                ..self
+            };
+        }
+
         let clock_rate = (clock_rate as f32).clamp(0.01, 100.0).to_bits();
 
         // SAFETY: The minimum value is 0.01 so its bits can never be fully
@@ -112,13 +171,99 @@ impl OsuStars {
         }
     }
 
+    /// Remove a previously specified clock rate so that the clock rate based
+    /// on the mods is used again.
+    pub const fn reset_clock_rate(mut self) -> Self {
+        self.clock_rate = None;
+
+        self
+    }
+
+    /// Override a beatmap's set AR, e.g. for the difficulty adjust mod.
+    ///
+    /// Mods such as HR or DT still apply on top of the given value.
+    ///
+    /// | Minimum | Maximum |
+    /// | :-----: | :-----: |
+    /// | -10     | 11      |
+    pub fn ar(mut self, ar: f32) -> Self {
+        self.ar = Some(ar.clamp(-10.0, 11.0));
+
+        self
+    }
+
+    /// Override a beatmap's set CS, e.g. for the difficulty adjust mod.
+    ///
+    /// Mods such as HR or DT still apply on top of the given value.
+    ///
+    /// | Minimum | Maximum |
+    /// | :-----: | :-----: |
+    /// | 0       | 11      |
+    pub fn cs(mut self, cs: f32) -> Self {
+        self.cs = Some(cs.clamp(0.0, 11.0));
+
+        self
+    }
+
+    /// Override a beatmap's set HP, e.g. for the difficulty adjust mod.
+    ///
+    /// Mods such as HR or DT still apply on top of the given value.
+    ///
+    /// | Minimum | Maximum |
+    /// | :-----: | :-----: |
+    /// | 0       | 11      |
+    pub fn hp(mut self, hp: f32) -> Self {
+        self.hp = Some(hp.clamp(0.0, 11.0));
+
+        self
+    }
+
+    /// Override a beatmap's set OD, e.g. for the difficulty adjust mod.
+    ///
+    /// Mods such as HR or DT still apply on top of the given value.
+    ///
+    /// | Minimum | Maximum |
+    /// | :-----: | :-----: |
+    /// | 0       | 11      |
+    pub fn od(mut self, od: f32) -> Self {
+        self.od = Some(od.clamp(0.0, 11.0));
+
+        self
+    }
+
     /// Perform the difficulty calculation.
+    ///
+    /// If the map cannot be converted to the required mode, default
+    /// attributes are returned. Use [`try_calculate`](Self::try_calculate)
+    /// to handle that case explicitly.
     pub fn calculate(&self, map: &Beatmap) -> OsuDifficultyAttributes {
-        let Ok(map) = map.convert_ref(GameMode::Osu, &self.mods.into()) else {
-            return Default::default();
-        };
+        self.try_calculate(map).unwrap_or_default()
+    }
 
-        let map = map.as_ref();
+    /// Perform the difficulty calculation.
+    ///
+    /// Returns an error if the map cannot be converted to the required mode.
+    pub fn try_calculate(&self, map: &Beatmap) -> Result<OsuDifficultyAttributes, ConvertError> {
+        let map = convert_ref(map, GameMode::Osu, &self.mods.into())?;
+
+        self.calculate_converted(map.as_ref())
+    }
+
+    /// Perform the difficulty calculation on a map that is already an
+    /// osu!standard map.
+    ///
+    /// No conversion is attempted. Returns an error if the map's mode is not
+    /// [`GameMode::Osu`].
+    pub fn calculate_converted(
+        &self,
+        map: &Beatmap,
+    ) -> Result<OsuDifficultyAttributes, ConvertError> {
+        if map.mode != GameMode::Osu {
+            return Err(ConvertError::Convert {
+                from: map.mode,
+                to: GameMode::Osu,
+            });
+        }
 
         let DifficultyValues {
             skills:
@@ -131,6 +276,9 @@ impl OsuStars {
             mut attrs,
         } = DifficultyValues::calculate(self, map);
 
+        let aim_difficult_strain_count = aim.count_difficult_strains();
+        let speed_difficult_strain_count = speed.count_difficult_strains();
+
         let aim_difficulty_value = aim.difficulty_value();
         let aim_no_sliders_difficulty_value = aim_no_sliders.difficulty_value();
         let speed_relevant_note_count = speed.relevant_note_count();
@@ -149,7 +297,203 @@ impl OsuStars {
             flashlight_difficulty_value,
         );
 
-        attrs
+        attrs.aim_difficult_strain_count = aim_difficult_strain_count;
+        attrs.speed_difficult_strain_count = speed_difficult_strain_count;
+
+        Ok(attrs)
+    }
+
+    /// Perform the difficulty calculation but instead of evaluating the skill
+    /// strains, return them as is.
+    ///
+    /// Suitable to plot the difficulty of a map over time.
+    pub fn strains(&self, map: &Beatmap) -> OsuStrains {
+        let Ok(map) = convert_ref(map, GameMode::Osu, &self.mods.into()) else {
+            return OsuStrains::default();
+        };
+
+        let DifficultyValues {
+            skills:
+                OsuSkills {
+                    aim,
+                    aim_no_sliders,
+                    speed,
+                    flashlight,
+                },
+            attrs: _,
+        } = DifficultyValues::calculate(self, map.as_ref());
+
+        OsuStrains {
+            aim: aim.get_curr_strain_peaks().into_vec(),
+            aim_no_sliders: aim_no_sliders.get_curr_strain_peaks().into_vec(),
+            speed: speed.get_curr_strain_peaks().into_vec(),
+            flashlight: flashlight.get_curr_strain_peaks().into_vec(),
+        }
+    }
+
+    /// Perform the difficulty calculation but instead of evaluating the aim
+    /// skill, return the strain of each individual object.
+    ///
+    /// The values are aligned to the difficulty objects, i.e. index 0
+    /// corresponds to the second hit object because the first hit object
+    /// has no difficulty object.
+    pub fn object_aim_strains(&self, map: &Beatmap) -> Vec<f64> {
+        let Ok(map) = convert_ref(map, GameMode::Osu, &self.mods.into()) else {
+            return Vec::new();
+        };
+
+        let DifficultyValues { skills, attrs: _ } = DifficultyValues::calculate(self, map.as_ref());
+
+        skills.aim.into_object_strains()
+    }
+
+    /// Calculate only the number of notes that are relevant to speed.
+    ///
+    /// The value is the same as
+    /// [`OsuDifficultyAttributes::speed_note_count`] but no skill is
+    /// evaluated.
+    pub fn speed_note_count(&self, map: &Beatmap) -> f64 {
+        let Ok(map) = convert_ref(map, GameMode::Osu, &self.mods.into()) else {
+            return 0.0;
+        };
+
+        let DifficultyValues { skills, attrs: _ } = DifficultyValues::calculate(self, map.as_ref());
+
+        skills.speed.relevant_note_count()
+    }
+
+    /// Calculate the star rating at each of the given times.
+    ///
+    /// The times are in milliseconds and already adjusted by the clock rate,
+    /// e.g. with DT the last object of a 90 seconds map is at 60 seconds.
+    ///
+    /// The difficulty is only evaluated after each object so for times in
+    /// between two objects, the star rating is linearly interpolated. Times
+    /// before the first or after the last object are clamped to the map's
+    /// bounds. The results are in the same order as the given times.
+    pub fn stars_at(&self, map: &Beatmap, times: &[f64]) -> Vec<f64> {
+        let mut stars = vec![0.0; times.len()];
+
+        let Ok(map) = convert_ref(map, GameMode::Osu, &self.mods.into()) else {
+            return stars;
+        };
+
+        let map = map.as_ref();
+        let mods = self.get_mods();
+        let take = self.get_passed_objects();
+
+        let OsuDifficultySetup {
+            scaling_factor,
+            map_attrs,
+            mut attrs,
+            time_preempt,
+        } = OsuDifficultySetup::new(self, map);
+
+        let mut osu_objects = convert_objects(
+            map,
+            &scaling_factor,
+            mods.hr(),
+            time_preempt,
+            take,
+            &mut attrs,
+        );
+
+        let osu_object_iter = osu_objects.iter_mut().map(Pin::new);
+
+        let diff_objects =
+            DifficultyValues::create_difficulty_objects(self, &scaling_factor, osu_object_iter);
+
+        // The first hit object has no difficulty object
+        let take_diff_objects = cmp::min(map.hit_objects.len(), take).saturating_sub(1);
+        let diff_objects = &diff_objects[..cmp::min(take_diff_objects, diff_objects.len())];
+
+        let mut skills = OsuSkills::new(mods, &scaling_factor, &map_attrs, time_preempt);
+
+        let mut aim = Skill::new(&mut skills.aim, diff_objects);
+        let mut aim_no_sliders = Skill::new(&mut skills.aim_no_sliders, diff_objects);
+        let mut speed = Skill::new(&mut skills.speed, diff_objects);
+        let mut flashlight = Skill::new(&mut skills.flashlight, diff_objects);
+
+        let mut pending: Vec<usize> = (0..times.len()).collect();
+        pending.sort_by(|&a, &b| times[a].total_cmp(&times[b]));
+        let mut pending = pending.into_iter().peekable();
+
+        // Time and star rating after the previous object
+        let mut prev: Option<(f64, f64)> = None;
+
+        for (i, hit_object) in diff_objects.iter().enumerate() {
+            aim.process(hit_object);
+            aim_no_sliders.process(hit_object);
+            speed.process(hit_object);
+            flashlight.process(hit_object);
+
+            let curr_time = hit_object.start_time;
+
+            // Only evaluate if a requested time requires the current value,
+            // either now or for the interpolation after the next object.
+            let Some(&next_idx) = pending.peek() else {
+                break;
+            };
+
+            let required = diff_objects
+                .get(i + 1)
+                .map_or(true, |next| times[next_idx] <= next.start_time);
+
+            if !required {
+                prev = None;
+
+                continue;
+            }
+
+            let mut curr_attrs = attrs.clone();
+
+            DifficultyValues::eval(
+                &mut curr_attrs,
+                mods,
+                aim.inner.as_difficulty_value(),
+                aim_no_sliders.inner.as_difficulty_value(),
+                speed.inner.as_difficulty_value(),
+                speed.inner.relevant_note_count(),
+                flashlight.inner.as_difficulty_value(),
+            );
+
+            let curr_stars = curr_attrs.stars;
+            let (prev_time, prev_stars) = prev.unwrap_or((curr_time, curr_stars));
+
+            while let Some(idx) = pending.next_if(|&idx| times[idx] <= curr_time) {
+                stars[idx] = if curr_time > prev_time {
+                    let t = ((times[idx] - prev_time) / (curr_time - prev_time)).max(0.0);
+
+                    prev_stars + (curr_stars - prev_stars) * t
+                } else {
+                    prev_stars
+                };
+            }
+
+            prev = Some((curr_time, curr_stars));
+        }
+
+        // Times after the last object
+        if let Some((_, last_stars)) = prev {
+            for idx in pending {
+                stars[idx] = last_stars;
+            }
+        }
+
+        stars
+    }
+
+    /// The mods' bit values that are used in the calculation.
+    pub const fn effective_mods(&self) -> u32 {
+        self.mods
+    }
+
+    /// The clock rate that is used in the calculation.
+    ///
+    /// This is either the specified clock rate or, if none was specified, the
+    /// one based on the mods e.g. 1.5 for DT.
+    pub fn effective_clock_rate(&self) -> f64 {
+        self.get_clock_rate()
     }
 
     pub(crate) const fn get_mods(&self) -> u32 {
@@ -159,11 +503,16 @@ impl OsuStars {
     pub(crate) fn get_clock_rate(&self) -> f64 {
         let clock_rate = self
             .clock_rate
-            .map_or(self.mods.clock_rate() as f32, non_zero_u32_to_f32);
+            .map_or_else(|| self.get_mods_clock_rate(), non_zero_u32_to_f32);
 
         f64::from(clock_rate)
     }
 
+    fn get_mods_clock_rate(&self) -> f32 {
+        self.mods_clock_rate
+            .unwrap_or_else(|| self.mods.clock_rate() as f32)
+    }
+
     pub(crate) fn get_passed_objects(&self) -> usize {
         self.passed_objects.map_or(usize::MAX, |n| n as usize)
     }
@@ -179,16 +528,40 @@ impl Debug for OsuStars {
             mods,
             passed_objects,
             clock_rate,
+            mods_clock_rate,
+            ar,
+            cs,
+            hp,
+            od,
         } = self;
 
         f.debug_struct("OsuStars")
             .field("mods", mods)
             .field("passed_objects", passed_objects)
             .field("clock_rate", &clock_rate.map(non_zero_u32_to_f32))
+            .field("mods_clock_rate", mods_clock_rate)
+            .field("ar", ar)
+            .field("cs", cs)
+            .field("hp", hp)
+            .field("od", od)
             .finish()
     }
 }
 
+/// Calculate the difficulty of multiple maps in parallel.
+///
+/// Every map is calculated independently with the same settings so the
+/// results are the same as calling [`OsuStars::calculate`] on each map
+/// sequentially and they are in the same order as the given maps.
+#[cfg(feature = "rayon")]
+pub fn stars_bulk(maps: &[Beatmap], difficulty: &OsuStars) -> Vec<OsuDifficultyAttributes> {
+    use rayon::prelude::*;
+
+    maps.par_iter()
+        .map(|map| difficulty.calculate(map))
+        .collect()
+}
+
 impl Default for OsuStars {
     fn default() -> Self {
         Self::new()
@@ -205,7 +578,25 @@ pub struct OsuDifficultySetup {
 impl OsuDifficultySetup {
     pub fn new(difficulty: &OsuStars, map: &Beatmap) -> Self {
         let clock_rate = difficulty.get_clock_rate();
-        let map_attrs = map.attributes().mods(difficulty.get_mods()).build();
+        let mut map_attrs = map.attributes().mods(difficulty.get_mods());
+
+        if let Some(ar) = difficulty.ar {
+            map_attrs = map_attrs.ar(ar, false);
+        }
+
+        if let Some(cs) = difficulty.cs {
+            map_attrs = map_attrs.cs(cs, false);
+        }
+
+        if let Some(hp) = difficulty.hp {
+            map_attrs = map_attrs.hp(hp, false);
+        }
+
+        if let Some(od) = difficulty.od {
+            map_attrs = map_attrs.od(od, false);
+        }
+
+        let map_attrs = map_attrs.build();
         let scaling_factor = ScalingFactor::new(map_attrs.cs);
 
         let attrs = OsuDifficultyAttributes {
@@ -381,3 +772,176 @@ impl DifficultyValues {
             .collect()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use rosu_pp::model::mods::rosu_mods::{
+        generated_mods::DoubleTimeOsu, GameMod, GameMods as GameModsLazer, GameModsIntermode,
+    };
+
+    use super::*;
+
+    fn parse_map(mode: u8, hit_objects: &str) -> Beatmap {
+        let content = format!(
+            "osu file format v14
+
+[General]
+Mode: {mode}
+
+[Difficulty]
+HPDrainRate:5
+CircleSize:4
+OverallDifficulty:8
+ApproachRate:9
+SliderMultiplier:1.4
+SliderTickRate:1
+
+[TimingPoints]
+0,300,4,2,0,100,1,0
+
+[HitObjects]
+{hit_objects}"
+        );
+
+        Beatmap::from_bytes(content.as_bytes()).unwrap()
+    }
+
+    fn jumps() -> Beatmap {
+        let hit_objects = "64,64,1000,1,0,0:0:0:0:
+448,64,1150,1,0,0:0:0:0:
+448,320,1300,2,0,B|320:320|192:320,1,140
+64,320,1750,1,0,0:0:0:0:
+256,192,1900,1,0,0:0:0:0:
+64,64,2050,1,0,0:0:0:0:
+";
+
+        parse_map(0, hit_objects)
+    }
+
+    fn stream() -> Beatmap {
+        let hit_objects: String = (0..100)
+            .map(|i| {
+                format!(
+                    "{},192,{},1,0,0:0:0:0:\n",
+                    200 + (i % 4) * 30,
+                    1000 + i * 75
+                )
+            })
+            .collect();
+
+        parse_map(0, &hit_objects)
+    }
+
+    #[test]
+    fn converted_path_matches_conversion() {
+        let map = jumps();
+        let difficulty = OsuStars::new().mods(8 + 64);
+
+        let attrs = difficulty.calculate(&map);
+        assert!(attrs.stars > 0.0);
+        assert_eq!(difficulty.calculate_converted(&map), Ok(attrs));
+
+        let taiko = Beatmap {
+            mode: GameMode::Taiko,
+            ..map
+        };
+
+        assert_eq!(
+            difficulty.calculate_converted(&taiko),
+            Err(ConvertError::Convert {
+                from: GameMode::Taiko,
+                to: GameMode::Osu
+            })
+        );
+    }
+
+    #[test]
+    fn object_aim_strains_per_difficulty_object() {
+        let map = jumps();
+        let strains = OsuStars::new().object_aim_strains(&map);
+
+        assert_eq!(strains.len(), map.hit_objects.len() - 1);
+        assert!(strains.iter().all(|strain| strain.is_finite()));
+        assert!(strains.iter().any(|&strain| strain > 0.0));
+    }
+
+    #[test]
+    fn stream_speed_note_count() {
+        let map = stream();
+        let difficulty = OsuStars::new();
+
+        let speed_note_count = difficulty.speed_note_count(&map);
+        let attrs = difficulty.calculate(&map);
+
+        assert_eq!(speed_note_count, attrs.speed_note_count);
+        assert!(speed_note_count > 0.6 * map.hit_objects.len() as f64);
+    }
+
+    #[test]
+    fn intermode_mods_match_bits() {
+        let intermode = OsuStars::new().mods(GameModsIntermode::from_acronyms("HDDT"));
+        let bits = OsuStars::new().mods(8 + 64);
+
+        assert_eq!(intermode.effective_mods(), bits.effective_mods());
+        assert_eq!(
+            intermode.effective_clock_rate(),
+            bits.effective_clock_rate()
+        );
+        assert_eq!(bits.effective_clock_rate(), 1.5);
+
+        let nightcore = OsuStars::new().mods(GameModsIntermode::from_acronyms("NC"));
+        assert!(nightcore.effective_mods().dt());
+        assert_eq!(nightcore.effective_clock_rate(), 1.5);
+    }
+
+    #[test]
+    fn lazer_mods_keep_custom_rate() {
+        let mut mods = GameModsLazer::new();
+
+        mods.insert(GameMod::DoubleTimeOsu(DoubleTimeOsu {
+            speed_change: Some(1.2),
+            ..Default::default()
+        }));
+
+        let difficulty = OsuStars::new().mods(mods);
+
+        assert_eq!(difficulty.effective_mods(), 64);
+        assert!((difficulty.effective_clock_rate() - 1.2).abs() < 1e-6);
+
+        let difficulty = difficulty.clock_rate(1.1);
+        assert!((difficulty.effective_clock_rate() - 1.1).abs() < 1e-6);
+
+        let difficulty = difficulty.reset_clock_rate();
+        assert!((difficulty.effective_clock_rate() - 1.2).abs() < 1e-6);
+    }
+
+    #[test]
+    fn clock_rate_overrides_mods() {
+        let mods_only = OsuStars::new().mods(64);
+        assert_eq!(mods_only.effective_clock_rate(), 1.5);
+
+        let overridden = mods_only.clock_rate(0.9);
+        assert!((overridden.effective_clock_rate() - 0.9).abs() < 1e-6);
+
+        let reset = overridden.reset_clock_rate();
+        assert_eq!(reset.effective_clock_rate(), 1.5);
+        assert_eq!(
+            OsuStars::new().reset_clock_rate().effective_clock_rate(),
+            1.0
+        );
+    }
+
+    #[test]
+    fn non_finite_clock_rate_is_ignored() {
+        for clock_rate in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            let difficulty = OsuStars::new().clock_rate(clock_rate);
+            assert_eq!(difficulty.effective_clock_rate(), 1.0);
+
+            let difficulty = OsuStars::new().clock_rate(1.2).clock_rate(clock_rate);
+            assert_eq!(difficulty.effective_clock_rate(), 1.0);
+
+            let difficulty = OsuStars::new().mods(64).clock_rate(clock_rate);
+            assert_eq!(difficulty.effective_clock_rate(), 1.5);
+        }
+    }
+}
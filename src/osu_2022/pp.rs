@@ -2,13 +2,17 @@ use std::cmp;
 
 use rosu_pp::{
     any::HitResultPriority,
+    model::mods::GameMods,
     osu::{OsuScoreOrigin, OsuScoreState},
     Beatmap,
 };
 
-use super::{OsuDifficultyAttributes, OsuPerformanceAttributes, OsuStars};
+use super::{
+    OsuDifficultyAttributes, OsuPerformanceAttributes, OsuPerformanceBreakdown, OsuSkillBreakdown,
+    OsuStars,
+};
 
-use crate::util::{float_ext::FloatExt, mods::Mods};
+use crate::util::{accuracy::osu_accuracy, float_ext::FloatExt, mods::Mods};
 
 /// Performance calculator on osu!standard maps.
 #[derive(Clone, Debug, PartialEq)]
@@ -23,7 +27,12 @@ pub struct OsuPP<'map> {
     n100: Option<u32>,
     n50: Option<u32>,
     misses: Option<u32>,
+    large_tick_hits: Option<u32>,
+    slider_end_hits: Option<u32>,
     hitresult_priority: HitResultPriority,
+    lazer: bool,
+    infer_misses: bool,
+    estimate_accuracy: bool,
 }
 
 impl<'map> OsuPP<'map> {
@@ -39,7 +48,12 @@ impl<'map> OsuPP<'map> {
             n100: None,
             n50: None,
             misses: None,
+            large_tick_hits: None,
+            slider_end_hits: None,
             hitresult_priority: HitResultPriority::default(),
+            lazer: false,
+            infer_misses: false,
+            estimate_accuracy: false,
         }
     }
 
@@ -55,8 +69,20 @@ impl<'map> OsuPP<'map> {
 
     /// Specify mods.
     ///
+    /// Accepted types are
+    /// - `u32`
+    /// - [`rosu_mods::GameModsLegacy`]
+    /// - [`rosu_mods::GameMods`]
+    /// - [`rosu_mods::GameModsIntermode`]
+    /// - [`&rosu_mods::GameModsIntermode`](rosu_mods::GameModsIntermode)
+    ///
+    /// Mods are converted to their legacy bit values so lazer mods without
+    /// a legacy counterpart are ignored. The custom speed change of lazer
+    /// rate changing mods is kept and used unless a clock rate is specified
+    /// through [`clock_rate`](Self::clock_rate).
+    ///
     /// See <https://github.com/ppy/osu-api/wiki#mods>
-    pub fn mods(mut self, mods: u32) -> Self {
+    pub fn mods(mut self, mods: impl Into<GameMods>) -> Self {
         self.difficulty = self.difficulty.mods(mods);
 
         self
@@ -106,6 +132,70 @@ impl<'map> OsuPP<'map> {
         self
     }
 
+    /// Whether the calculated attributes belong to an osu!lazer or osu!stable
+    /// score.
+    ///
+    /// If `true`, slider heads, ticks, repeats, and ends are considered for
+    /// the accuracy, otherwise only the 300s, 100s, 50s, and misses are.
+    ///
+    /// Defaults to `false`.
+    pub const fn lazer(mut self, lazer: bool) -> Self {
+        self.lazer = lazer;
+
+        self
+    }
+
+    /// Whether the amount of misses should be inferred from the combo if
+    /// [`misses`](Self::misses) was not specified.
+    ///
+    /// The inference uses the same full-combo threshold as the effective miss
+    /// count so it is only an estimate; it cannot tell misses apart from
+    /// slider breaks.
+    ///
+    /// Defaults to `false`.
+    pub const fn infer_misses_from_combo(mut self, infer: bool) -> Self {
+        self.infer_misses = infer;
+
+        self
+    }
+
+    /// Whether hitresults should be estimated from the combo if neither the
+    /// accuracy nor any of the 300s, 100s, and 50s were specified.
+    ///
+    /// The effective miss count guesses the amount of misses and slider
+    /// breaks from the combo. Every such break that is not a miss is assumed
+    /// to be a 100 and all other objects are counted as 300s. This is the
+    /// highest accuracy for which the effective miss count of the play is the
+    /// one suggested by the combo.
+    ///
+    /// Defaults to `false`.
+    pub const fn estimate_accuracy_from_combo(mut self, estimate: bool) -> Self {
+        self.estimate_accuracy = estimate;
+
+        self
+    }
+
+    /// Specify the amount of "large tick" hits, i.e. hit slider ticks and
+    /// repeats.
+    ///
+    /// Only relevant for osu!lazer scores. If unspecified, all of them are
+    /// considered to be hit.
+    pub const fn large_tick_hits(mut self, large_tick_hits: u32) -> Self {
+        self.large_tick_hits = Some(large_tick_hits);
+
+        self
+    }
+
+    /// Specify the amount of hit slider ends.
+    ///
+    /// Only relevant for osu!lazer scores. If unspecified, all of them are
+    /// considered to be hit.
+    pub const fn slider_end_hits(mut self, slider_end_hits: u32) -> Self {
+        self.slider_end_hits = Some(slider_end_hits);
+
+        self
+    }
+
     /// Use the specified settings of the given [`Difficulty`].
     pub fn difficulty(mut self, difficulty: OsuStars) -> Self {
         self.difficulty = difficulty;
@@ -149,9 +239,9 @@ impl<'map> OsuPP<'map> {
             n100,
             n50,
             misses,
-            large_tick_hits: _,
+            large_tick_hits,
             small_tick_hits: _,
-            slider_end_hits: _,
+            slider_end_hits,
         } = state;
 
         self.combo = Some(max_combo);
@@ -159,18 +249,46 @@ impl<'map> OsuPP<'map> {
         self.n100 = Some(n100);
         self.n50 = Some(n50);
         self.misses = Some(misses);
+        self.large_tick_hits = Some(large_tick_hits);
+        self.slider_end_hits = Some(slider_end_hits);
 
         self
     }
 
     /// Specify the accuracy of a play between `0.0` and `100.0`.
     /// This will be used to generate matching hitresults.
+    ///
+    /// A `NaN` or infinite value is ignored, i.e. hitresults are generated as
+    /// if no accuracy was specified.
     pub fn accuracy(mut self, acc: f64) -> Self {
-        self.acc = Some(acc.clamp(0.0, 100.0) / 100.0);
+        self.acc = if !acc.is_finite() {
+            None
+        } else {
+            Some(acc.clamp(0.0, 100.0) / 100.0)
+        };
 
         self
     }
 
+    /// Calculate the performance of an average player on the map.
+    ///
+    /// The given function maps the map's star rating to the accuracy between
+    /// `0.0` and `100.0` that is expected for that star rating, e.g.
+    /// [`expected_accuracy`]. All other settings are kept as is.
+    pub fn calculate_expected(
+        mut self,
+        acc_for_stars: impl FnOnce(f64) -> f64,
+    ) -> OsuPerformanceAttributes {
+        let attrs = self
+            .attributes
+            .take()
+            .unwrap_or_else(|| self.difficulty.calculate(self.map));
+
+        let acc = acc_for_stars(attrs.stars);
+
+        self.attributes(attrs).accuracy(acc).calculate()
+    }
+
     /// Create the [`OsuScoreState`] that will be used for performance calculation.
     #[allow(clippy::too_many_lines)]
     fn generate_state(&mut self) -> (OsuScoreState, OsuDifficultyAttributes) {
@@ -186,9 +304,25 @@ impl<'map> OsuPP<'map> {
         );
         let priority = self.hitresult_priority;
 
-        let misses = self.misses.map_or(0, |n| cmp::min(n, n_objects));
+        let misses = match (self.misses, self.combo) {
+            (Some(misses), _) => cmp::min(misses, n_objects),
+            (None, Some(combo)) if self.infer_misses => {
+                cmp::min(infer_misses(&attrs, combo), n_objects)
+            }
+            (None, _) => 0,
+        };
+
         let n_remaining = n_objects - misses;
 
+        let estimated_n100 = match (self.acc, self.n300, self.n100, self.n50, self.combo) {
+            (None, None, None, None, Some(combo)) if self.estimate_accuracy => {
+                let breaks = combo_based_miss_count(&attrs, combo).ceil() as u32;
+
+                Some(cmp::min(breaks.saturating_sub(misses), n_remaining))
+            }
+            _ => None,
+        };
+
         let mut n300 = self.n300.map_or(0, |n| cmp::min(n, n_remaining));
         let mut n100 = self.n100.map_or(0, |n| cmp::min(n, n_remaining));
         let mut n50 = self.n50.map_or(0, |n| cmp::min(n, n_remaining));
@@ -220,7 +354,7 @@ impl<'map> OsuPP<'map> {
 
                     for new100 in min_n100..=max_n100 {
                         let new50 = n_remaining - new100;
-                        let dist = (acc - accuracy(n300, new100, new50, misses)).abs();
+                        let dist = (acc - osu_accuracy(n300, new100, new50, misses)).abs();
 
                         if dist < best_dist {
                             best_dist = dist;
@@ -241,7 +375,7 @@ impl<'map> OsuPP<'map> {
 
                     for new300 in min_n300..=max_n300 {
                         let new50 = n_remaining - new300;
-                        let curr_dist = (acc - accuracy(new300, n100, new50, misses)).abs();
+                        let curr_dist = (acc - osu_accuracy(new300, n100, new50, misses)).abs();
 
                         if curr_dist < best_dist {
                             best_dist = curr_dist;
@@ -265,7 +399,7 @@ impl<'map> OsuPP<'map> {
 
                     for new300 in min_n300..=max_n300 {
                         let new100 = n_remaining - new300;
-                        let curr_dist = (acc - accuracy(new300, new100, n50, misses)).abs();
+                        let curr_dist = (acc - osu_accuracy(new300, new100, n50, misses)).abs();
 
                         if curr_dist < best_dist {
                             best_dist = curr_dist;
@@ -288,7 +422,8 @@ impl<'map> OsuPP<'map> {
 
                         for new100 in min_n100..=max_n100 {
                             let new50 = n_remaining - new300 - new100;
-                            let curr_dist = (acc - accuracy(new300, new100, new50, misses)).abs();
+                            let curr_dist =
+                                (acc - osu_accuracy(new300, new100, new50, misses)).abs();
 
                             if curr_dist < best_dist {
                                 best_dist = curr_dist;
@@ -317,6 +452,9 @@ impl<'map> OsuPP<'map> {
                     }
                 }
             }
+        } else if let Some(estimated_n100) = estimated_n100 {
+            n100 = estimated_n100;
+            n300 = n_remaining - n100;
         } else {
             let remaining = n_objects.saturating_sub(n300 + n100 + n50 + misses);
 
@@ -342,38 +480,234 @@ impl<'map> OsuPP<'map> {
             cmp::min(combo, max_possible_combo)
         });
 
+        let (large_tick_hits, slider_end_hits) = if self.lazer {
+            let large_tick_hits = self
+                .large_tick_hits
+                .map_or(attrs.n_large_ticks, |n| cmp::min(n, attrs.n_large_ticks));
+
+            let slider_end_hits = self
+                .slider_end_hits
+                .map_or(attrs.n_sliders, |n| cmp::min(n, attrs.n_sliders));
+
+            (large_tick_hits, slider_end_hits)
+        } else {
+            (0, 0)
+        };
+
         let state = OsuScoreState {
             max_combo,
             n300,
             n100,
             n50,
             misses,
-            large_tick_hits: 0,
+            large_tick_hits,
             small_tick_hits: 0,
-            slider_end_hits: 0,
+            slider_end_hits,
         };
 
         (state, attrs)
     }
 
+    const fn score_origin(&self, attrs: &OsuDifficultyAttributes) -> OsuScoreOrigin {
+        score_origin(attrs, self.lazer)
+    }
+
     /// Calculate all performance related values, including pp and stars.
-    pub fn calculate(mut self) -> OsuPerformanceAttributes {
+    pub fn calculate(self) -> OsuPerformanceAttributes {
+        self.calculate_with_breakdown().0
+    }
+
+    /// Calculate all performance related values and additionally return the
+    /// multipliers that were applied to the aim and speed portions.
+    ///
+    /// The performance attributes are the same as those of
+    /// [`calculate`](Self::calculate).
+    pub fn calculate_with_breakdown(
+        mut self,
+    ) -> (OsuPerformanceAttributes, OsuPerformanceBreakdown) {
         let (state, attrs) = self.generate_state();
 
         let effective_miss_count = calculate_effective_misses(&attrs, &state);
+        let acc = state.accuracy(self.score_origin(&attrs));
 
         let inner = OsuPerformanceInner {
             attrs,
             mods: self.difficulty.get_mods(),
-            acc: state.accuracy(OsuScoreOrigin::Stable),
+            acc,
             state,
             effective_miss_count,
         };
 
-        inner.calculate()
+        inner.calculate_with_breakdown()
     }
+
+    /// Calculate the performance as if the play was a full combo.
+    ///
+    /// Hitresults are generated as usual, then every miss is re-distributed
+    /// onto 300s, 100s, and 50s according to their current ratio, i.e. the
+    /// amount of 100s becomes `floor(n100 * n_objects / (n300 + n100 + n50))`,
+    /// analogous for 50s, and the remainder are 300s. If there were no hits
+    /// at all, all objects are counted as 300s. The combo is set to the map's
+    /// max combo.
+    ///
+    /// Unlike setting the accuracy to 100% this roughly preserves the
+    /// player's accuracy while removing combo breaks.
+    pub fn calculate_if_fc(mut self) -> OsuPerformanceAttributes {
+        let (state, attrs) = self.generate_state();
+
+        let n_hits = state.n300 + state.n100 + state.n50;
+        let n_objects = n_hits + state.misses;
+
+        let scale = |n: u32| {
+            if n_hits == 0 {
+                0
+            } else {
+                (f64::from(n) * f64::from(n_objects) / f64::from(n_hits)).floor() as u32
+            }
+        };
+
+        let n100 = scale(state.n100);
+        let n50 = scale(state.n50);
+        let n300 = n_objects.saturating_sub(n100 + n50);
+
+        self.n300 = Some(n300);
+        self.n100 = Some(n100);
+        self.n50 = Some(n50);
+        self.misses = Some(0);
+        self.combo = Some(attrs.max_combo);
+        self.large_tick_hits = None;
+        self.slider_end_hits = None;
+        self.attributes = Some(attrs);
+
+        self.calculate()
+    }
+
+    /// Calculate the performance for both the best and the worst case
+    /// distribution of unspecified hitresults.
+    ///
+    /// The difficulty attributes are calculated only once. The previously
+    /// specified [`HitResultPriority`] is ignored.
+    ///
+    /// Returns the attributes of the best case first and the worst case second.
+    pub fn calculate_bounds(mut self) -> (OsuPerformanceAttributes, OsuPerformanceAttributes) {
+        let attrs = self
+            .attributes
+            .take()
+            .unwrap_or_else(|| self.difficulty.calculate(self.map));
+
+        let best = self
+            .clone()
+            .attributes(attrs.clone())
+            .hitresult_priority(HitResultPriority::BestCase)
+            .calculate();
+
+        let worst = self
+            .attributes(attrs)
+            .hitresult_priority(HitResultPriority::WorstCase)
+            .calculate();
+
+        (best, worst)
+    }
+
+    /// Calculate the performance for each of the given accuracies.
+    ///
+    /// The difficulty attributes are calculated only once and reused for
+    /// every accuracy. Specified 300s, 100s, and 50s are ignored so that the
+    /// accuracies take effect, all other settings are kept as is.
+    ///
+    /// The results are in the same order as the given accuracies.
+    pub fn calculate_for_accuracies(&mut self, accs: &[f64]) -> Vec<OsuPerformanceAttributes> {
+        let attrs = self
+            .attributes
+            .take()
+            .unwrap_or_else(|| self.difficulty.calculate(self.map));
+
+        let results = accs
+            .iter()
+            .map(|&acc| {
+                let mut calc = self.clone().attributes(attrs.clone()).accuracy(acc);
+                calc.n300 = None;
+                calc.n100 = None;
+                calc.n50 = None;
+
+                calc.calculate()
+            })
+            .collect();
+
+        self.attributes = Some(attrs);
+
+        results
+    }
+
+    /// Calculate the highest pp that can be achieved on the map with the
+    /// current mods.
+    ///
+    /// In this version's formulas, misses, a lower combo, and 100s or 50s
+    /// instead of 300s never increase the pp so the highest pp are those of a
+    /// full combo SS. For osu!lazer scores, all slider ticks and ends are
+    /// considered to be hit. Previously specified hitresults, accuracy, and
+    /// combo are ignored.
+    pub fn theoretical_max_pp(mut self) -> OsuPerformanceAttributes {
+        self.acc = None;
+        self.combo = None;
+        self.n300 = None;
+        self.n100 = None;
+        self.n50 = None;
+        self.misses = None;
+        self.large_tick_hits = None;
+        self.slider_end_hits = None;
+        self.hitresult_priority = HitResultPriority::BestCase;
+
+        self.calculate()
+    }
+
+    /// Calculate the flashlight portion of the pp for every combo from `0` up
+    /// to the highest combo that is reachable with the given misses.
+    ///
+    /// Hitresults are generated once and only the combo is varied, i.e. the
+    /// accuracy stays the same for every entry. The effective miss count is
+    /// re-evaluated for each combo, including the adjustment for RX. Note that
+    /// the values will all be `0.0` if the mods don't include FL.
+    pub fn flashlight_combo_curve(mut self) -> Vec<(u32, f64)> {
+        let (state, attrs) = self.generate_state();
+        let mods = self.difficulty.get_mods();
+        let acc = state.accuracy(self.score_origin(&attrs));
+        let max_possible_combo = attrs.max_combo.saturating_sub(state.misses);
+
+        (0..=max_possible_combo)
+            .map(|combo| {
+                let state = OsuScoreState {
+                    max_combo: combo,
+                    ..state.clone()
+                };
+
+                let mut inner = OsuPerformanceInner {
+                    effective_miss_count: calculate_effective_misses(&attrs, &state),
+                    attrs: attrs.clone(),
+                    mods,
+                    acc,
+                    state,
+                };
+
+                inner.apply_relax_misses();
+
+                (combo, inner.compute_flashlight_value())
+            })
+            .collect()
+    }
+}
+
+/// Rough accuracy between `0.0` and `100.0` that an average player achieves
+/// on a map of the given star rating.
+///
+/// Starts at 99% for maps up to 2 stars and drops by half a percent per star
+/// until it reaches 94% at 12 stars, e.g. 97% for 6 stars.
+pub fn expected_accuracy(stars: f64) -> f64 {
+    (99.0 - 0.5 * (stars - 2.0).max(0.0)).max(94.0)
 }
 
+/// Raised to `1.14` in this version, scaling the combined aim, speed,
+/// accuracy, and flashlight pp.
 pub const PERFORMANCE_BASE_MULTIPLIER: f64 = 1.14;
 
 struct OsuPerformanceInner {
@@ -385,14 +719,17 @@ struct OsuPerformanceInner {
 }
 
 impl OsuPerformanceInner {
-    fn calculate(mut self) -> OsuPerformanceAttributes {
+    fn calculate_with_breakdown(mut self) -> (OsuPerformanceAttributes, OsuPerformanceBreakdown) {
+        let mut breakdown = OsuPerformanceBreakdown::default();
         let total_hits = self.state.total_hits();
 
         if total_hits == 0 {
-            return OsuPerformanceAttributes {
+            let attrs = OsuPerformanceAttributes {
                 difficulty: self.attrs,
                 ..Default::default()
             };
+
+            return (attrs, breakdown);
         }
 
         let total_hits = f64::from(total_hits);
@@ -407,30 +744,11 @@ impl OsuPerformanceInner {
             multiplier *= 1.0 - (f64::from(self.attrs.n_spinners) / total_hits).powf(0.85);
         }
 
-        if self.mods.rx() {
-            // * https://www.desmos.com/calculator/bc9eybdthb
-            // * we use OD13.3 as maximum since it's the value at which great hitwidow becomes 0
-            // * this is well beyond currently maximum achievable OD which is 12.17 (DTx2 + DA with OD11)
-            let (n100_mult, n50_mult) = if self.attrs.od > 0.0 {
-                (
-                    1.0 - (self.attrs.od / 13.33).powf(1.8),
-                    1.0 - (self.attrs.od / 13.33).powf(5.0),
-                )
-            } else {
-                (1.0, 1.0)
-            };
+        self.apply_relax_misses();
 
-            // * As we're adding Oks and Mehs to an approximated number of combo breaks the result can be
-            // * higher than total hits in specific scenarios (which breaks some calculations) so we need to clamp it.
-            self.effective_miss_count = (self.effective_miss_count
-                + f64::from(self.state.n100)
-                + n100_mult
-                + f64::from(self.state.n50) * n50_mult)
-                .min(total_hits);
-        }
-
-        let aim_value = self.compute_aim_value();
-        let speed_value = self.compute_speed_value();
+        let relevant_acc = self.compute_relevant_acc();
+        let aim_value = self.compute_aim_value(&mut breakdown.aim);
+        let speed_value = self.compute_speed_value(relevant_acc, &mut breakdown.speed);
         let acc_value = self.compute_accuracy_value();
         let flashlight_value = self.compute_flashlight_value();
 
@@ -441,7 +759,7 @@ impl OsuPerformanceInner {
         .powf(1.0 / 1.1)
             * multiplier;
 
-        OsuPerformanceAttributes {
+        let attrs = OsuPerformanceAttributes {
             difficulty: self.attrs,
             pp_acc: acc_value,
             pp_aim: aim_value,
@@ -449,11 +767,41 @@ impl OsuPerformanceInner {
             pp_speed: speed_value,
             pp,
             effective_miss_count: self.effective_miss_count,
+            relevant_acc,
+            total_hits: self.state.total_hits(),
+        };
+
+        (attrs, breakdown)
+    }
+
+    fn apply_relax_misses(&mut self) {
+        if !self.mods.rx() {
+            return;
         }
+
+        // * https://www.desmos.com/calculator/bc9eybdthb
+        // * we use OD13.3 as maximum since it's the value at which great hitwidow becomes 0
+        // * this is well beyond currently maximum achievable OD which is 12.17 (DTx2 + DA with OD11)
+        let (n100_mult, n50_mult) = if self.attrs.od > 0.0 {
+            (
+                1.0 - (self.attrs.od / 13.33).powf(1.8),
+                1.0 - (self.attrs.od / 13.33).powf(5.0),
+            )
+        } else {
+            (1.0, 1.0)
+        };
+
+        // * As we're adding Oks and Mehs to an approximated number of combo breaks the result can be
+        // * higher than total hits in specific scenarios (which breaks some calculations) so we need to clamp it.
+        self.effective_miss_count = (self.effective_miss_count
+            + f64::from(self.state.n100) * n100_mult
+            + f64::from(self.state.n50) * n50_mult)
+            .min(self.total_hits());
     }
 
-    fn compute_aim_value(&self) -> f64 {
+    fn compute_aim_value(&self, breakdown: &mut OsuSkillBreakdown) -> f64 {
         let mut aim_value = (5.0 * (self.attrs.aim / 0.0675).max(1.0) - 4.0).powf(3.0) / 100_000.0;
+        *breakdown = OsuSkillBreakdown::new(aim_value);
 
         let total_hits = self.total_hits();
 
@@ -462,16 +810,22 @@ impl OsuPerformanceInner {
             + f64::from(u8::from(total_hits > 2000.0)) * (total_hits / 2000.0).log10() * 0.5;
 
         aim_value *= len_bonus;
+        breakdown.len_bonus = len_bonus;
 
         // * Penalize misses by assessing # of misses relative to the total # of objects.
         // * Default a 3% reduction for any # of misses.
         if self.effective_miss_count > 0.0 {
-            aim_value *= 0.97
+            let miss_penalty = 0.97
                 * (1.0 - (self.effective_miss_count / total_hits).powf(0.775))
                     .powf(self.effective_miss_count);
+
+            aim_value *= miss_penalty;
+            breakdown.miss_penalty = miss_penalty;
         }
 
-        aim_value *= self.get_combo_scaling_factor();
+        let combo_scaling = self.get_combo_scaling_factor();
+        aim_value *= combo_scaling;
+        breakdown.combo_scaling = combo_scaling;
 
         let ar_factor = if self.mods.rx() {
             0.0
@@ -484,11 +838,15 @@ impl OsuPerformanceInner {
         };
 
         // * Buff for longer maps with high AR.
-        aim_value *= 1.0 + ar_factor * len_bonus;
+        let ar_bonus = 1.0 + ar_factor * len_bonus;
+        aim_value *= ar_bonus;
+        breakdown.ar_bonus = ar_bonus;
 
         if self.mods.hd() {
             // * We want to give more reward for lower AR when it comes to aim and HD. This nerfs high AR and buffs lower AR.
-            aim_value *= 1.0 + 0.04 * (12.0 - self.attrs.ar);
+            let hidden_bonus = 1.0 + 0.04 * (12.0 - self.attrs.ar);
+            aim_value *= hidden_bonus;
+            breakdown.hidden_bonus = hidden_bonus;
         }
 
         // * We assume 15% of sliders in a map are difficult since there's no way to tell from the performance calculator.
@@ -505,22 +863,30 @@ impl OsuPerformanceInner {
                 + self.attrs.slider_factor;
 
             aim_value *= slider_nerf_factor;
+            breakdown.slider_nerf = slider_nerf_factor;
         }
 
         aim_value *= self.acc;
+        breakdown.accuracy = self.acc;
+
         // * It is important to consider accuracy difficulty when scaling with accuracy.
-        aim_value *= 0.98 + self.attrs.od.powf(2.0) / 2500.0;
+        let od_scaling = 0.98 + self.attrs.od.powf(2.0) / 2500.0;
+        aim_value *= od_scaling;
+        breakdown.od = od_scaling;
 
         aim_value
     }
 
-    fn compute_speed_value(&self) -> f64 {
+    fn compute_speed_value(&self, relevant_acc: f64, breakdown: &mut OsuSkillBreakdown) -> f64 {
         if self.mods.rx() {
+            *breakdown = OsuSkillBreakdown::new(0.0);
+
             return 0.0;
         }
 
         let mut speed_value =
             (5.0 * (self.attrs.speed / 0.0675).max(1.0) - 4.0).powf(3.0) / 100_000.0;
+        *breakdown = OsuSkillBreakdown::new(speed_value);
 
         let total_hits = self.total_hits();
 
@@ -529,16 +895,22 @@ impl OsuPerformanceInner {
             + f64::from(u8::from(total_hits > 2000.0)) * (total_hits / 2000.0).log10() * 0.5;
 
         speed_value *= len_bonus;
+        breakdown.len_bonus = len_bonus;
 
         // * Penalize misses by assessing # of misses relative to the total # of objects.
         // * Default a 3% reduction for any # of misses.
         if self.effective_miss_count > 0.0 {
-            speed_value *= 0.97
+            let miss_penalty = 0.97
                 * (1.0 - (self.effective_miss_count / total_hits).powf(0.775))
                     .powf(self.effective_miss_count.powf(0.875));
+
+            speed_value *= miss_penalty;
+            breakdown.miss_penalty = miss_penalty;
         }
 
-        speed_value *= self.get_combo_scaling_factor();
+        let combo_scaling = self.get_combo_scaling_factor();
+        speed_value *= combo_scaling;
+        breakdown.combo_scaling = combo_scaling;
 
         let ar_factor = if self.attrs.ar > 10.33 {
             0.3 * (self.attrs.ar - 10.33)
@@ -547,14 +919,44 @@ impl OsuPerformanceInner {
         };
 
         // * Buff for longer maps with high AR.
-        speed_value *= 1.0 + ar_factor * len_bonus;
+        let ar_bonus = 1.0 + ar_factor * len_bonus;
+        speed_value *= ar_bonus;
+        breakdown.ar_bonus = ar_bonus;
 
         if self.mods.hd() {
             // * We want to give more reward for lower AR when it comes to aim and HD.
             // * This nerfs high AR and buffs lower AR.
-            speed_value *= 1.0 + 0.04 * (12.0 - self.attrs.ar);
+            let hidden_bonus = 1.0 + 0.04 * (12.0 - self.attrs.ar);
+            speed_value *= hidden_bonus;
+            breakdown.hidden_bonus = hidden_bonus;
         }
 
+        // * Scale the speed value with accuracy and OD.
+        let od_scaling = 0.95 + self.attrs.od * self.attrs.od / 750.0;
+        let acc_scaling =
+            ((self.acc + relevant_acc) / 2.0).powf((14.5 - (self.attrs.od).max(8.0)) / 2.0);
+        speed_value *= od_scaling * acc_scaling;
+        breakdown.od = od_scaling;
+        breakdown.accuracy = acc_scaling;
+
+        // * Scale the speed value with # of 50s to punish doubletapping.
+        let n50_penalty = 0.99_f64.powf(
+            f64::from(u8::from(f64::from(self.state.n50) >= total_hits / 500.0))
+                * (f64::from(self.state.n50) - total_hits / 500.0),
+        );
+        speed_value *= n50_penalty;
+        breakdown.n50_penalty = n50_penalty;
+
+        speed_value
+    }
+
+    fn compute_relevant_acc(&self) -> f64 {
+        if self.attrs.speed_note_count.eq(0.0) {
+            return 0.0;
+        }
+
+        let total_hits = self.total_hits();
+
         // * Calculate accuracy assuming the worst case scenario
         let relevant_total_diff = total_hits - self.attrs.speed_note_count;
         let relevant_n300 = (f64::from(self.state.n300) - relevant_total_diff).max(0.0);
@@ -565,24 +967,8 @@ impl OsuPerformanceInner {
             - (relevant_total_diff - f64::from(self.state.n300 + self.state.n100)).max(0.0))
         .max(0.0);
 
-        let relevant_acc = if self.attrs.speed_note_count.eq(0.0) {
-            0.0
-        } else {
-            (relevant_n300 * 6.0 + relevant_n100 * 2.0 + relevant_n50)
-                / (self.attrs.speed_note_count * 6.0)
-        };
-
-        // * Scale the speed value with accuracy and OD.
-        speed_value *= (0.95 + self.attrs.od * self.attrs.od / 750.0)
-            * ((self.acc + relevant_acc) / 2.0).powf((14.5 - (self.attrs.od).max(8.0)) / 2.0);
-
-        // * Scale the speed value with # of 50s to punish doubletapping.
-        speed_value *= 0.99_f64.powf(
-            f64::from(u8::from(f64::from(self.state.n50) >= total_hits / 500.0))
-                * (f64::from(self.state.n50) - total_hits / 500.0),
-        );
-
-        speed_value
+        (relevant_n300 * 6.0 + relevant_n100 * 2.0 + relevant_n50)
+            / (self.attrs.speed_note_count * 6.0)
     }
 
     fn compute_accuracy_value(&self) -> f64 {
@@ -676,32 +1062,371 @@ impl OsuPerformanceInner {
     }
 }
 
-fn calculate_effective_misses(attrs: &OsuDifficultyAttributes, state: &OsuScoreState) -> f64 {
-    // * Guess the number of misses + slider breaks from combo
-    let mut combo_based_miss_count = 0.0;
-
-    if attrs.n_sliders > 0 {
-        let full_combo_threshold = f64::from(attrs.max_combo) - 0.1 * f64::from(attrs.n_sliders);
+/// The accuracy of the given [`OsuScoreState`] between `0.0` and `1.0`.
+///
+/// If `lazer` is `true`, slider ticks and ends are considered, just like for
+/// [`OsuPP::lazer`]. This is the accuracy that is used for the pp calculation.
+pub fn score_accuracy(state: &OsuScoreState, attrs: &OsuDifficultyAttributes, lazer: bool) -> f64 {
+    state.accuracy(score_origin(attrs, lazer))
+}
 
-        if f64::from(state.max_combo) < full_combo_threshold {
-            combo_based_miss_count = full_combo_threshold / f64::from(state.max_combo).max(1.0);
+/// The [`OsuScoreOrigin`] that is used to evaluate the accuracy of an
+/// [`OsuScoreState`].
+///
+/// For osu!lazer scores, the maximum amount of large ticks and slider ends
+/// are taken from the attributes. Since mods are converted to their legacy
+/// bits, the Classic mod has no effect; such scores should be treated as
+/// osu!stable scores, i.e. `lazer` should be `false`.
+pub const fn score_origin(attrs: &OsuDifficultyAttributes, lazer: bool) -> OsuScoreOrigin {
+    if lazer {
+        OsuScoreOrigin::WithSliderAcc {
+            max_large_ticks: attrs.n_large_ticks,
+            max_slider_ends: attrs.n_sliders,
         }
+    } else {
+        OsuScoreOrigin::Stable
     }
+}
+
+fn calculate_effective_misses(attrs: &OsuDifficultyAttributes, state: &OsuScoreState) -> f64 {
+    // * Guess the number of misses + slider breaks from combo
+    let combo_based_miss_count = combo_based_miss_count(attrs, state.max_combo);
 
     // * Clamp miss count to maximum amount of possible breaks
-    combo_based_miss_count =
+    let combo_based_miss_count =
         combo_based_miss_count.min(f64::from(state.n100 + state.n50 + state.misses));
 
     combo_based_miss_count.max(f64::from(state.misses))
 }
 
-fn accuracy(n300: u32, n100: u32, n50: u32, misses: u32) -> f64 {
-    if n300 + n100 + n50 + misses == 0 {
+fn infer_misses(attrs: &OsuDifficultyAttributes, combo: u32) -> u32 {
+    combo_based_miss_count(attrs, combo).floor() as u32
+}
+
+/// The amount of misses and slider breaks that the given combo suggests.
+fn combo_based_miss_count(attrs: &OsuDifficultyAttributes, combo: u32) -> f64 {
+    if attrs.n_sliders == 0 {
         return 0.0;
     }
 
-    let numerator = 6 * n300 + 2 * n100 + n50;
-    let denominator = 6 * (n300 + n100 + n50 + misses);
+    let full_combo_threshold = f64::from(attrs.max_combo) - 0.1 * f64::from(attrs.n_sliders);
+
+    if f64::from(combo) < full_combo_threshold {
+        full_combo_threshold / f64::from(combo).max(1.0)
+    } else {
+        0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attributes() -> OsuDifficultyAttributes {
+        OsuDifficultyAttributes {
+            n_circles: 300,
+            n_sliders: 200,
+            n_spinners: 0,
+            max_combo: 900,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn inferred_misses_match_effective_misses() {
+        let attrs = attributes();
+
+        for combo in [10, 50, 200, 500, 879, 880, 900] {
+            let state = OsuScoreState {
+                max_combo: combo,
+                n300: 400,
+                n100: 100,
+                ..Default::default()
+            };
+
+            let effective_misses = calculate_effective_misses(&attrs, &state);
+
+            assert_eq!(infer_misses(&attrs, combo), effective_misses.floor() as u32);
+        }
+    }
+
+    fn estimated_state(combo: u32, misses: u32) -> OsuScoreState {
+        let map = Beatmap::default();
+
+        OsuPP::new(&map)
+            .attributes(attributes())
+            .estimate_accuracy_from_combo(true)
+            .combo(combo)
+            .misses(misses)
+            .generate_state()
+            .0
+    }
+
+    #[test]
+    fn estimated_accuracy_full_combo() {
+        let state = estimated_state(900, 0);
+
+        assert_eq!(state.n300, 500);
+        assert_eq!(state.n100 + state.n50 + state.misses, 0);
+    }
+
+    #[test]
+    fn estimated_accuracy_keeps_effective_misses() {
+        let attrs = attributes();
+
+        for (combo, misses) in [(100, 2), (250, 0), (10, 30), (600, 1)] {
+            let state = estimated_state(combo, misses);
+            assert_eq!(state.n300 + state.n100 + state.n50 + state.misses, 500);
+            assert_eq!(state.n50, 0);
+
+            let expected = combo_based_miss_count(&attrs, combo).max(f64::from(misses));
+            let effective_misses = calculate_effective_misses(&attrs, &state);
+            assert!((effective_misses - expected).abs() < 1e-9);
+
+            // * One 100 less would no longer explain all breaks
+            if state.n100 > 0 {
+                let better = OsuScoreState {
+                    n300: state.n300 + 1,
+                    n100: state.n100 - 1,
+                    ..state.clone()
+                };
+
+                assert!(calculate_effective_misses(&attrs, &better) < expected);
+            }
+        }
+    }
+
+    #[test]
+    fn relax_effective_misses_weight_n100() {
+        let map = Beatmap::default();
+
+        let attrs = OsuDifficultyAttributes {
+            od: 10.0,
+            ..attributes()
+        };
+
+        let pp = OsuPP::new(&map)
+            .attributes(attrs)
+            .mods(128)
+            .n100(10)
+            .n50(0)
+            .misses(0)
+            .calculate();
+
+        let expected = 10.0 * (1.0 - (10.0_f64 / 13.33).powf(1.8));
+
+        assert!((pp.effective_miss_count - expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn expected_accuracy_per_stars() {
+        for stars in [0.0, 1.5, 2.0] {
+            assert!(expected_accuracy(stars).eq(99.0));
+        }
+
+        assert!(expected_accuracy(6.0).eq(97.0));
+
+        for stars in [12.0, 13.5, 20.0] {
+            assert!(expected_accuracy(stars).eq(94.0));
+        }
+    }
+
+    #[test]
+    fn expected_matches_accuracy() {
+        let map = Beatmap::default();
+
+        let attrs = OsuDifficultyAttributes {
+            aim: 3.0,
+            speed: 2.5,
+            speed_note_count: 250.0,
+            od: 8.0,
+            stars: 6.0,
+            ..attributes()
+        };
+
+        let calc = OsuPP::new(&map).attributes(attrs).mods(8);
+
+        let expected = calc.clone().calculate_expected(|_| 95.0);
+        let with_acc = calc.clone().accuracy(95.0).calculate();
+        assert_eq!(expected, with_acc);
+
+        let expected = calc.clone().calculate_expected(expected_accuracy);
+        let with_acc = calc.accuracy(97.0).calculate();
+        assert_eq!(expected, with_acc);
+    }
+
+    #[test]
+    fn total_hits_of_passed_objects() {
+        let map = Beatmap::default();
+
+        let pp = OsuPP::new(&map).attributes(attributes()).calculate();
+        assert_eq!(pp.total_hits(), 500);
+
+        let calc = OsuPP::new(&map)
+            .attributes(attributes())
+            .passed_objects(100)
+            .misses(2);
+
+        let (state, _) = calc.clone().generate_state();
+        assert_eq!(state.total_hits(), 100);
+        assert_eq!(calc.calculate().total_hits(), 100);
+    }
+
+    #[test]
+    fn breakdown_reproduces_skill_values() {
+        let map = Beatmap::default();
+
+        let attrs = OsuDifficultyAttributes {
+            aim: 3.0,
+            speed: 2.5,
+            slider_factor: 0.95,
+            speed_note_count: 250.0,
+            ar: 10.3,
+            od: 8.0,
+            ..attributes()
+        };
+
+        let calc = OsuPP::new(&map)
+            .attributes(attrs)
+            .mods(8)
+            .combo(600)
+            .n100(15)
+            .n50(3)
+            .misses(2);
+
+        let (pp, breakdown) = calc.clone().calculate_with_breakdown();
+        assert_eq!(pp, calc.calculate());
+
+        for (value, skill) in [(pp.pp_aim, breakdown.aim), (pp.pp_speed, breakdown.speed)] {
+            assert!(value > 0.0);
+            assert!((skill.value() - value).abs() < value * 1e-12);
+            assert!(skill.miss_penalty < 1.0);
+            assert!(skill.combo_scaling < 1.0);
+            assert!(skill.hidden_bonus > 1.0);
+        }
+
+        assert!(breakdown.aim.slider_nerf < 1.0);
+        assert!(breakdown.speed.n50_penalty < 1.0);
+    }
+
+    #[test]
+    fn flashlight_combo_curve_ends_at_calculated_value() {
+        let map = Beatmap::default();
 
-    f64::from(numerator) / f64::from(denominator)
+        let attrs = OsuDifficultyAttributes {
+            flashlight: 2.5,
+            od: 9.0,
+            ..attributes()
+        };
+
+        for mods in [1024, 1024 + 128] {
+            let calc = OsuPP::new(&map)
+                .attributes(attrs.clone())
+                .mods(mods)
+                .n100(10)
+                .misses(2);
+
+            let curve = calc.clone().flashlight_combo_curve();
+            let pp = calc.combo(898).calculate();
+
+            assert_eq!(curve.len(), 899);
+
+            let (combo, last) = curve[curve.len() - 1];
+            assert_eq!(combo, 898);
+            assert!(last.eq(pp.pp_flashlight));
+            assert!(curve.windows(2).all(|w| w[0].1 <= w[1].1));
+        }
+    }
+
+    #[test]
+    fn converted_november_attributes_use_overall_accuracy() {
+        let map = Beatmap::default();
+
+        let attrs = crate::osu_2021_november::OsuDifficultyAttributes {
+            aim_strain: 3.0,
+            speed_strain: 2.5,
+            od: 8.0,
+            n_circles: 300,
+            n_sliders: 200,
+            max_combo: 900,
+            ..Default::default()
+        };
+
+        let attrs = OsuDifficultyAttributes::from(attrs);
+        assert!(attrs.speed_note_count.eq(500.0));
+
+        let pp = OsuPP::new(&map)
+            .attributes(attrs)
+            .accuracy(95.0)
+            .calculate();
+        let (state, attrs) = OsuPP::new(&map)
+            .attributes(pp.difficulty.clone())
+            .accuracy(95.0)
+            .generate_state();
+
+        let acc = state.accuracy(score_origin(&attrs, false));
+        assert!((pp.relevant_acc - acc).abs() < 1e-9);
+    }
+
+    #[test]
+    fn theoretical_max_pp_is_ss() {
+        let map = Beatmap::default();
+
+        let attrs = OsuDifficultyAttributes {
+            aim: 3.0,
+            speed: 2.5,
+            flashlight: 2.0,
+            slider_factor: 0.98,
+            speed_note_count: 250.0,
+            ar: 9.0,
+            od: 8.0,
+            ..attributes()
+        };
+
+        for mods in [0, 8 + 64 + 1024, 128] {
+            let calc = OsuPP::new(&map).attributes(attrs.clone()).mods(mods);
+
+            let max = calc
+                .clone()
+                .misses(3)
+                .combo(200)
+                .n100(20)
+                .theoretical_max_pp();
+            let ss = calc.clone().accuracy(100.0).calculate();
+
+            assert!(max.pp.eq(ss.pp));
+
+            for acc in [99.9, 98.0, 95.0, 90.0] {
+                assert!(calc.clone().accuracy(acc).calculate().pp <= max.pp);
+            }
+        }
+    }
+
+    #[test]
+    fn non_finite_accuracy_is_ignored() {
+        let map = Beatmap::default();
+
+        let calc = OsuPP::new(&map).attributes(attributes()).n100(20);
+        let (expected, _) = calc.clone().generate_state();
+
+        for acc in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            let (state, _) = calc.clone().accuracy(acc).generate_state();
+            assert_eq!(state, expected);
+        }
+    }
+
+    #[test]
+    fn estimated_accuracy_ignored_with_accuracy() {
+        let map = Beatmap::default();
+
+        let (state, attrs) = OsuPP::new(&map)
+            .attributes(attributes())
+            .estimate_accuracy_from_combo(true)
+            .combo(100)
+            .accuracy(95.0)
+            .generate_state();
+
+        let acc = state.accuracy(score_origin(&attrs, false));
+        assert!((acc - 0.95).abs() < 0.001);
+    }
 }
@@ -19,14 +19,25 @@ const STRAIN_DECAY_BASE: f64 = 0.15;
 pub struct Aim {
     with_sliders: bool,
     curr_strain: f64,
+    object_strains: Vec<f64>,
     inner: OsuStrainSkill,
 }
 
 impl Aim {
     pub fn new(with_sliders: bool) -> Self {
+        // Object strains are only required for the difficult strain count
+        // and the object strains of the aim skill with sliders
+        let object_strains = if with_sliders {
+            // mean=406.72 | median=307
+            Vec::with_capacity(256)
+        } else {
+            Vec::new()
+        };
+
         Self {
             with_sliders,
             curr_strain: 0.0,
+            object_strains,
             inner: OsuStrainSkill::default(),
         }
     }
@@ -53,6 +64,14 @@ impl Aim {
             OsuStrainSkill::DIFFICULTY_MULTIPLER,
         )
     }
+
+    pub fn count_difficult_strains(&self) -> f64 {
+        OsuStrainSkill::count_difficult_strains(&self.object_strains, self.as_difficulty_value())
+    }
+
+    pub fn into_object_strains(self) -> Vec<f64> {
+        self.object_strains
+    }
 }
 
 impl ISkill for Aim {
@@ -107,6 +126,10 @@ impl<'a> Skill<'a, Aim> {
             AimEvaluator::evaluate_diff_of(curr, self.diff_objects, self.inner.with_sliders)
                 * SKILL_MULTIPLIER;
 
+        if self.inner.with_sliders {
+            self.inner.object_strains.push(self.inner.curr_strain);
+        }
+
         self.inner.curr_strain
     }
 }
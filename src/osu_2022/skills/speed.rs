@@ -61,6 +61,10 @@ impl Speed {
         )
     }
 
+    pub fn count_difficult_strains(&self) -> f64 {
+        OsuStrainSkill::count_difficult_strains(&self.object_strains, self.as_difficulty_value())
+    }
+
     pub fn relevant_note_count(&self) -> f64 {
         self.object_strains
             .iter()
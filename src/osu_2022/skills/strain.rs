@@ -54,6 +54,24 @@ impl OsuStrainSkill {
 
         difficulty * difficulty_multiplier
     }
+
+    /// Weighted amount of object strains that are close to the top strain.
+    ///
+    /// This stems from a later osu!lazer version; osu!lazer did not track it
+    /// at the time so the value is only an approximation for this version.
+    pub fn count_difficult_strains(object_strains: &[f64], difficulty_value: f64) -> f64 {
+        if difficulty_value <= 0.0 {
+            return 0.0;
+        }
+
+        // * What would the top strain be if all strain values were identical
+        let consistent_top_strain = difficulty_value / 10.0;
+
+        // * Use a weighted sum of all strains. Constants are arbitrary and give nice values
+        object_strains.iter().fold(0.0, |sum, strain| {
+            sum + 1.1 / (1.0 + (-10.0 * (strain / consistent_top_strain - 0.88)).exp())
+        })
+    }
 }
 
 fn lerp(start: f64, end: f64, amount: f64) -> f64 {
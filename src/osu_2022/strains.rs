@@ -0,0 +1,22 @@
+/// The result of calculating the strains on an osu!standard map.
+///
+/// Suitable to plot the difficulty of a map over time.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct OsuStrains {
+    /// Strain peaks of the aim skill.
+    pub aim: Vec<f64>,
+    /// Strain peaks of the aim skill without sliders.
+    pub aim_no_sliders: Vec<f64>,
+    /// Strain peaks of the speed skill.
+    pub speed: Vec<f64>,
+    /// Strain peaks of the flashlight skill.
+    pub flashlight: Vec<f64>,
+}
+
+impl OsuStrains {
+    /// Time between two strains in ms.
+    ///
+    /// Note that the time is adjusted by the clock rate, e.g. with DT a
+    /// section covers 600ms of the original map.
+    pub const SECTION_LEN: f64 = 400.0;
+}
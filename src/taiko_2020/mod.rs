@@ -215,6 +215,7 @@ fn norm(p: f64, a: f64, b: f64, c: f64) -> f64 {
 
 /// The result of a difficulty calculation on an osu!taiko map.
 #[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TaikoDifficultyAttributes {
     /// The final star rating.
     pub stars: f64,
@@ -232,6 +233,7 @@ impl TaikoDifficultyAttributes {
 
 /// The result of a performance calculation on an osu!taiko map.
 #[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TaikoPerformanceAttributes {
     /// The difficulty attributes that were used for the performance calculation
     pub difficulty: TaikoDifficultyAttributes,
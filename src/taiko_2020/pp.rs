@@ -4,6 +4,10 @@ use crate::util::{math::difficulty_range, mods::Mods};
 
 use super::{TaikoDifficultyAttributes, TaikoPerformanceAttributes, TaikoStars};
 
+/// Multiplier on the combined strain and accuracy pp of the 2020 taiko
+/// algorithm.
+pub const PERFORMANCE_BASE_MULTIPLIER: f64 = 1.1;
+
 /// Performance calculator on osu!taiko maps.
 ///
 /// # Example
@@ -193,7 +197,7 @@ struct TaikoPPInner<'map> {
 
 impl<'map> TaikoPPInner<'map> {
     fn calculate(self) -> TaikoPerformanceAttributes {
-        let mut multiplier = 1.1;
+        let mut multiplier = PERFORMANCE_BASE_MULTIPLIER;
 
         if self.mods.nf() {
             multiplier *= 0.9;
@@ -1,5 +1,8 @@
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
 /// The result of a difficulty calculation on an osu!taiko map.
 #[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TaikoDifficultyAttributes {
     /// The difficulty of the stamina skill.
     pub stamina: f64,
@@ -37,6 +40,7 @@ impl TaikoDifficultyAttributes {
 
 /// The result of a performance calculation on an osu!taiko map.
 #[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TaikoPerformanceAttributes {
     /// The difficulty attributes that were used for the performance calculation
     pub difficulty: TaikoDifficultyAttributes,
@@ -48,6 +52,8 @@ pub struct TaikoPerformanceAttributes {
     pub pp_difficulty: f64,
     /// Scaled miss count based on total hits.
     pub effective_miss_count: f64,
+    /// The amount of hitresults of the score, i.e. `n300 + n100 + misses`.
+    pub total_hits: u32,
 }
 
 impl TaikoPerformanceAttributes {
@@ -66,6 +72,14 @@ impl TaikoPerformanceAttributes {
         self.difficulty.max_combo
     }
 
+    /// Return the amount of hitresults of the score, i.e.
+    /// `n300 + n100 + misses`.
+    ///
+    /// For partial plays, this only covers the passed circles.
+    pub const fn total_hits(&self) -> u32 {
+        self.total_hits
+    }
+
     /// Whether the [`Beatmap`] was a convert i.e. an osu!standard map.
     ///
     /// [`Beatmap`]: crate::model::beatmap::Beatmap
@@ -74,8 +88,50 @@ impl TaikoPerformanceAttributes {
     }
 }
 
+/// Concise summary of the pp and its components, rounded to one decimal,
+/// e.g. `pp=312.4 (difficulty=180.2 acc=120.3)`.
+impl Display for TaikoPerformanceAttributes {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(
+            f,
+            "pp={:.1} (difficulty={:.1} acc={:.1})",
+            self.pp, self.pp_difficulty, self.pp_acc
+        )
+    }
+}
+
 impl From<TaikoPerformanceAttributes> for TaikoDifficultyAttributes {
     fn from(attributes: TaikoPerformanceAttributes) -> Self {
         attributes.difficulty
     }
 }
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_round_trip() {
+        let attrs = TaikoPerformanceAttributes {
+            difficulty: TaikoDifficultyAttributes {
+                stamina: 1.5,
+                rhythm: 0.75,
+                color: 1.25,
+                peak: 2.5,
+                hit_window: 35.5,
+                stars: 4.25,
+                max_combo: 1200,
+                is_convert: true,
+            },
+            pp: 210.5,
+            pp_acc: 80.25,
+            pp_difficulty: 120.125,
+            effective_miss_count: 2.0,
+            total_hits: 1200,
+        };
+
+        let json = serde_json::to_string(&attrs).unwrap();
+        let deserialized: TaikoPerformanceAttributes = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, attrs);
+    }
+}
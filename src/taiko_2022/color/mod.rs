@@ -16,3 +16,49 @@ pub struct TaikoDifficultyColor {
     pub alternating_mono_pattern: Option<Weak<AlternatingMonoPattern>>,
     pub repeating_hit_patterns: Option<RefCount<RepeatingHitPatterns>>,
 }
+
+impl TaikoDifficultyColor {
+    pub fn encoding(&self) -> TaikoColorEncoding {
+        let mono_streak = self.mono_streak.as_ref().and_then(Weak::upgrade);
+
+        let alternating_mono_pattern = self
+            .alternating_mono_pattern
+            .as_ref()
+            .and_then(Weak::upgrade);
+
+        TaikoColorEncoding {
+            mono_streak_len: mono_streak.map(|mono| mono.get().run_len()),
+            alternating_mono_pattern_len: alternating_mono_pattern
+                .map(|pattern| pattern.get().mono_streaks.len()),
+            repeating_hit_patterns_len: self
+                .repeating_hit_patterns
+                .as_ref()
+                .map(|patterns| patterns.get().alternating_mono_patterns.len()),
+            repetition_interval: self
+                .repeating_hit_patterns
+                .as_ref()
+                .map(|patterns| patterns.get().repetition_interval),
+        }
+    }
+}
+
+/// The color encoding that was assigned to an osu!taiko difficulty object.
+///
+/// An encoding is only assigned to the first object of a mono streak,
+/// alternating mono pattern, or repeating hit pattern. For all other objects
+/// the respective field is `None`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TaikoColorEncoding {
+    /// The amount of consecutive notes with the same color in the mono streak
+    /// that starts at this object.
+    pub mono_streak_len: Option<usize>,
+    /// The amount of mono streaks in the alternating mono pattern that starts
+    /// at this object.
+    pub alternating_mono_pattern_len: Option<usize>,
+    /// The amount of alternating mono patterns in the repeating hit pattern
+    /// that starts at this object.
+    pub repeating_hit_patterns_len: Option<usize>,
+    /// The repetition interval of the repeating hit pattern that starts at
+    /// this object.
+    pub repetition_interval: Option<usize>,
+}
@@ -9,11 +9,16 @@ use rosu_pp::{model::mode::GameMode, Beatmap};
 use skills::peaks::{Peaks, PeaksSkill};
 use taiko_object::TaikoObject;
 
-use crate::util::mods::Mods;
+use crate::util::{
+    convert::{convert_ref, ConvertError},
+    mods::Mods,
+};
 
 pub use self::{
     attributes::{TaikoDifficultyAttributes, TaikoPerformanceAttributes},
+    color::TaikoColorEncoding,
     pp::*,
+    strains::TaikoStrains,
 };
 
 mod attributes;
@@ -22,8 +27,13 @@ mod difficulty_object;
 mod pp;
 mod rhythm;
 mod skills;
+mod strains;
 mod taiko_object;
 
+/// Applied to the color, rhythm, stamina, and combined peak difficulty values
+/// which, unlike for osu!standard, are not square rooted first.
+pub const DIFFICULTY_MULTIPLIER: f64 = 1.35;
+
 /// Difficulty calculator on maps of any mode.
 ///
 /// # Example
@@ -51,6 +61,7 @@ pub struct TaikoStars {
     /// This allows for an optimization to reduce the struct size by storing its
     /// bits as a [`NonZeroU32`].
     clock_rate: Option<NonZeroU32>,
+    convert_override: Option<bool>,
 }
 
 impl TaikoStars {
@@ -60,6 +71,7 @@ impl TaikoStars {
             mods: 0,
             passed_objects: None,
             clock_rate: None,
+            convert_override: None,
         }
     }
 
@@ -89,10 +101,24 @@ impl TaikoStars {
     /// If none is specified, it will take the clock rate based on the mods
     /// i.e. 1.5 for DT, 0.75 for HT and 1.0 otherwise.
     ///
+    /// A specified clock rate always takes precedence over the mods, e.g.
+    /// DT with a clock rate of 0.9 uses 0.9. Use
+    /// [`reset_clock_rate`](Self::reset_clock_rate) to fall back to the mods.
+    ///
     /// | Minimum | Maximum |
     /// | :-----: | :-----: |
     /// | 0.01    | 100     |
+    ///
+    /// A `NaN` or infinite value is ignored, i.e. the clock rate based on the
+    /// mods is used.
     pub fn clock_rate(self, clock_rate: f64) -> Self {
+        if !clock_rate.is_finite() {
+            return Self {
+                clock_rate: None,
+                ..self
+            };
+        }
+
         let clock_rate = (clock_rate as f32).clamp(0.01, 100.0).to_bits();
 
         // SAFETY: The minimum value is 0.01 so its bits can never be fully
@@ -105,11 +131,41 @@ impl TaikoStars {
         }
     }
 
+    /// Remove a previously specified clock rate so that the clock rate based
+    /// on the mods is used again.
+    pub const fn reset_clock_rate(mut self) -> Self {
+        self.clock_rate = None;
+
+        self
+    }
+
+    /// Override whether the map should be treated as a convert.
+    ///
+    /// This only affects the star rating penalty for converts, the map is
+    /// still converted to osu!taiko if necessary. The resulting attributes'
+    /// `is_convert` field carries the overridden value.
+    ///
+    /// If none is specified, it will be determined by the map itself.
+    pub const fn convert_override(mut self, is_convert: bool) -> Self {
+        self.convert_override = Some(is_convert);
+
+        self
+    }
+
     /// Perform the difficulty calculation.
+    ///
+    /// If the map cannot be converted to the required mode, default
+    /// attributes are returned. Use [`try_calculate`](Self::try_calculate)
+    /// to handle that case explicitly.
     pub fn calculate(&self, map: &Beatmap) -> TaikoDifficultyAttributes {
-        let Ok(map) = map.convert_ref(GameMode::Taiko, &self.mods.into()) else {
-            return Default::default();
-        };
+        self.try_calculate(map).unwrap_or_default()
+    }
+
+    /// Perform the difficulty calculation.
+    ///
+    /// Returns an error if the map cannot be converted to the required mode.
+    pub fn try_calculate(&self, map: &Beatmap) -> Result<TaikoDifficultyAttributes, ConvertError> {
+        let map = convert_ref(map, GameMode::Taiko, &self.mods.into())?;
 
         let map = map.as_ref();
 
@@ -124,7 +180,7 @@ impl TaikoStars {
         let mut attrs = TaikoDifficultyAttributes {
             hit_window,
             max_combo,
-            is_convert: map.is_convert,
+            is_convert: self.convert_override.unwrap_or(map.is_convert),
             ..Default::default()
         };
 
@@ -141,7 +197,74 @@ impl TaikoStars {
             combined_rating,
         );
 
-        attrs
+        Ok(attrs)
+    }
+
+    /// Perform the difficulty calculation but instead of evaluating the skill
+    /// strains, return them as is.
+    ///
+    /// Suitable to plot the difficulty of a map over time.
+    pub fn strains(&self, map: &Beatmap) -> TaikoStrains {
+        let Ok(map) = convert_ref(map, GameMode::Taiko, &self.mods.into()) else {
+            return TaikoStrains::default();
+        };
+
+        let DifficultyValues {
+            peaks,
+            max_combo: _,
+        } = DifficultyValues::calculate(self, map.as_ref());
+
+        TaikoStrains {
+            color: peaks.color.get_curr_strain_peaks().into_vec(),
+            rhythm: peaks.rhythm.get_curr_strain_peaks().into_vec(),
+            stamina: peaks.stamina.get_curr_strain_peaks().into_vec(),
+        }
+    }
+
+    /// Perform the color preprocessing and return the color encoding that was
+    /// assigned to each difficulty object.
+    ///
+    /// Index 0 corresponds to the third hit object because the first two hit
+    /// objects have no difficulty object.
+    ///
+    /// Mostly useful for debugging purposes.
+    pub fn color_encodings(&self, map: &Beatmap) -> Vec<TaikoColorEncoding> {
+        let Ok(map) = convert_ref(map, GameMode::Taiko, &self.mods.into()) else {
+            return Vec::new();
+        };
+
+        let mut n_diff_objects = 0;
+        let mut max_combo = 0;
+
+        let diff_objects = DifficultyValues::create_difficulty_objects(
+            map.as_ref(),
+            self.get_passed_objects() as u32,
+            self.get_clock_rate(),
+            &mut max_combo,
+            &mut n_diff_objects,
+        );
+
+        // The first two hit objects have no difficulty object
+        n_diff_objects = n_diff_objects.saturating_sub(2);
+
+        diff_objects
+            .iter()
+            .take(n_diff_objects)
+            .map(|h| h.get().color.encoding())
+            .collect()
+    }
+
+    /// The mods' bit values that are used in the calculation.
+    pub const fn effective_mods(&self) -> u32 {
+        self.mods
+    }
+
+    /// The clock rate that is used in the calculation.
+    ///
+    /// This is either the specified clock rate or, if none was specified, the
+    /// one based on the mods e.g. 1.5 for DT.
+    pub fn effective_clock_rate(&self) -> f64 {
+        self.get_clock_rate()
     }
 
     pub(crate) const fn get_mods(&self) -> u32 {
@@ -171,12 +294,14 @@ impl Debug for TaikoStars {
             mods,
             passed_objects,
             clock_rate,
+            convert_override,
         } = self;
 
         f.debug_struct("TaikoStars")
             .field("mods", mods)
             .field("passed_objects", passed_objects)
             .field("clock_rate", &clock_rate.map(non_zero_u32_to_f32))
+            .field("convert_override", convert_override)
             .finish()
     }
 }
@@ -239,8 +364,6 @@ impl DifficultyValues {
         stamina_difficulty_value: f64,
         peaks_difficulty_value: f64,
     ) {
-        const DIFFICULTY_MULTIPLIER: f64 = 1.35;
-
         let color_rating = color_difficulty_value * DIFFICULTY_MULTIPLIER;
         let rhythm_rating = rhythm_difficulty_value * DIFFICULTY_MULTIPLIER;
         let stamina_rating = stamina_difficulty_value * DIFFICULTY_MULTIPLIER;
@@ -314,3 +437,162 @@ impl DifficultyValues {
         diff_objects
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn convert_override_toggles_penalty() {
+        let hit_objects: String = (0..60)
+            .map(|i| {
+                let hit_sound = if i % 3 == 0 { 2 } else { 0 };
+
+                format!(
+                    "{},192,{},1,{hit_sound},0:0:0:0:\n",
+                    64 + (i % 8) * 48,
+                    1000 + i * 150
+                )
+            })
+            .collect();
+
+        let content = format!(
+            "osu file format v14
+
+[General]
+Mode: 0
+
+[Difficulty]
+HPDrainRate:5
+CircleSize:4
+OverallDifficulty:8
+ApproachRate:9
+SliderMultiplier:1.4
+SliderTickRate:1
+
+[TimingPoints]
+0,300,4,2,0,100,1,0
+
+[HitObjects]
+{hit_objects}"
+        );
+
+        let map = Beatmap::from_bytes(content.as_bytes()).unwrap();
+
+        let auto = TaikoStars::new().calculate(&map);
+        let convert = TaikoStars::new().convert_override(true).calculate(&map);
+        let no_convert = TaikoStars::new().convert_override(false).calculate(&map);
+
+        assert!(auto.is_convert);
+        assert_eq!(auto, convert);
+        assert!(!no_convert.is_convert);
+        assert!(no_convert.stars > 0.0);
+
+        let penalty = if convert.color < 2.0 && convert.stamina > 8.0 {
+            0.925 * 0.8
+        } else {
+            0.925
+        };
+
+        assert!((convert.stars - no_convert.stars * penalty).abs() < 1e-9);
+    }
+
+    #[test]
+    fn mania_map_cannot_be_converted() {
+        let map = Beatmap {
+            mode: GameMode::Mania,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            TaikoStars::new().try_calculate(&map),
+            Err(ConvertError::Convert {
+                from: GameMode::Mania,
+                to: GameMode::Taiko,
+            })
+        );
+        assert_eq!(TaikoStars::new().calculate(&map).stars, 0.0);
+    }
+
+    #[test]
+    fn color_encodings_of_double_pattern() {
+        // don don kat kat don don ...
+        let hit_objects: String = (0..12)
+            .map(|i| {
+                let hit_sound = if (i / 2) % 2 == 0 { 0 } else { 8 };
+
+                format!("256,192,{},1,{hit_sound},0:0:0:0:\n", 1000 + i * 150)
+            })
+            .collect();
+
+        let content = format!(
+            "osu file format v14
+
+[General]
+Mode: 1
+
+[Difficulty]
+HPDrainRate:5
+CircleSize:5
+OverallDifficulty:5
+ApproachRate:5
+SliderMultiplier:1.4
+SliderTickRate:1
+
+[TimingPoints]
+0,300,4,2,0,100,1,0
+
+[HitObjects]
+{hit_objects}"
+        );
+
+        let map = Beatmap::from_bytes(content.as_bytes()).unwrap();
+        let encodings = TaikoStars::new().color_encodings(&map);
+
+        // The first two hit objects have no difficulty object
+        assert_eq!(encodings.len(), 10);
+
+        for (i, encoding) in encodings.iter().enumerate() {
+            let expected = (i % 2 == 0).then_some(2);
+            assert_eq!(encoding.mono_streak_len, expected, "index {i}");
+        }
+
+        // All mono streaks have the same length so they form a single
+        // alternating mono pattern
+        assert_eq!(encodings[0].alternating_mono_pattern_len, Some(5));
+        assert!(encodings[1..]
+            .iter()
+            .all(|encoding| encoding.alternating_mono_pattern_len.is_none()));
+        assert_eq!(encodings[0].repeating_hit_patterns_len, Some(1));
+    }
+
+    #[test]
+    fn clock_rate_overrides_mods() {
+        let mods_only = TaikoStars::new().mods(64);
+        assert_eq!(mods_only.effective_clock_rate(), 1.5);
+
+        let overridden = mods_only.clock_rate(0.9);
+        assert!((overridden.effective_clock_rate() - 0.9).abs() < 1e-6);
+
+        let reset = overridden.reset_clock_rate();
+        assert_eq!(reset.effective_clock_rate(), 1.5);
+        assert_eq!(
+            TaikoStars::new().reset_clock_rate().effective_clock_rate(),
+            1.0
+        );
+    }
+
+    #[test]
+    fn non_finite_clock_rate_is_ignored() {
+        for clock_rate in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            let difficulty = TaikoStars::new().clock_rate(clock_rate);
+            assert_eq!(difficulty.effective_clock_rate(), 1.0);
+
+            let difficulty = TaikoStars::new().clock_rate(1.2).clock_rate(clock_rate);
+            assert_eq!(difficulty.effective_clock_rate(), 1.0);
+
+            let difficulty = TaikoStars::new().mods(64).clock_rate(clock_rate);
+            assert_eq!(difficulty.effective_clock_rate(), 1.5);
+        }
+    }
+}
@@ -2,7 +2,7 @@ use std::cmp;
 
 use rosu_pp::{any::HitResultPriority, taiko::TaikoScoreState, Beatmap};
 
-use crate::util::mods::Mods;
+use crate::util::{accuracy::taiko_accuracy, mods::Mods};
 
 use super::{TaikoDifficultyAttributes, TaikoPerformanceAttributes, TaikoStars};
 
@@ -19,6 +19,7 @@ pub struct TaikoPP<'map> {
     n300: Option<u32>,
     n100: Option<u32>,
     misses: Option<u32>,
+    great_hit_window: Option<f64>,
 }
 
 impl<'map> TaikoPP<'map> {
@@ -33,6 +34,7 @@ impl<'map> TaikoPP<'map> {
             n300: None,
             n100: None,
             hitresult_priority: HitResultPriority::default(),
+            great_hit_window: None,
         }
     }
 
@@ -94,8 +96,15 @@ impl<'map> TaikoPP<'map> {
 
     /// Specify the accuracy of a play between `0.0` and `100.0`.
     /// This will be used to generate matching hitresults.
+    ///
+    /// A `NaN` or infinite value is ignored, i.e. hitresults are generated as
+    /// if no accuracy was specified.
     pub fn accuracy(mut self, acc: f64) -> Self {
-        self.acc = Some(acc.clamp(0.0, 100.0) / 100.0);
+        self.acc = if !acc.is_finite() {
+            None
+        } else {
+            Some(acc.clamp(0.0, 100.0) / 100.0)
+        };
 
         self
     }
@@ -133,6 +142,17 @@ impl<'map> TaikoPP<'map> {
         self
     }
 
+    /// Override the hit window for 300s in milliseconds that is used for the
+    /// accuracy portion of the performance.
+    ///
+    /// If none is specified, the hit window of the difficulty attributes is
+    /// used.
+    pub const fn great_hit_window(mut self, great_hit_window: f64) -> Self {
+        self.great_hit_window = Some(great_hit_window);
+
+        self
+    }
+
     /// Provide parameters through a [`TaikoScoreState`].
     #[allow(clippy::needless_pass_by_value)]
     pub const fn state(mut self, state: TaikoScoreState) -> Self {
@@ -193,7 +213,7 @@ impl<'map> TaikoPP<'map> {
 
                     for new300 in min_n300..=max_n300 {
                         let new100 = n_remaining - new300;
-                        let dist = (acc - accuracy(new300, new100, misses)).abs();
+                        let dist = (acc - taiko_accuracy(new300, new100, misses)).abs();
 
                         if dist < best_dist {
                             best_dist = dist;
@@ -243,6 +263,7 @@ impl<'map> TaikoPP<'map> {
         let inner = TaikoPerformanceInner {
             mods: self.difficulty.get_mods(),
             state,
+            hit_window: self.great_hit_window.unwrap_or(attrs.hit_window),
             attrs,
         };
 
@@ -250,10 +271,15 @@ impl<'map> TaikoPP<'map> {
     }
 }
 
+/// Multiplier on the combined difficulty and accuracy pp in the 2022 taiko
+/// algorithm.
+pub const PERFORMANCE_BASE_MULTIPLIER: f64 = 1.13;
+
 struct TaikoPerformanceInner {
     attrs: TaikoDifficultyAttributes,
     mods: u32,
     state: TaikoScoreState,
+    hit_window: f64,
 }
 
 impl TaikoPerformanceInner {
@@ -268,7 +294,7 @@ impl TaikoPerformanceInner {
             0.0
         };
 
-        let mut multiplier = 1.13;
+        let mut multiplier = PERFORMANCE_BASE_MULTIPLIER;
 
         if self.mods.hd() {
             multiplier *= 1.075;
@@ -289,6 +315,7 @@ impl TaikoPerformanceInner {
             pp_acc: acc_value,
             pp_difficulty: diff_value,
             effective_miss_count,
+            total_hits: self.state.total_hits(),
         }
     }
 
@@ -324,11 +351,11 @@ impl TaikoPerformanceInner {
     }
 
     fn compute_accuracy_value(&self) -> f64 {
-        if self.attrs.hit_window <= 0.0 {
+        if self.hit_window <= 0.0 {
             return 0.0;
         }
 
-        let mut acc_value = (60.0 / self.attrs.hit_window).powf(1.1)
+        let mut acc_value = (60.0 / self.hit_window).powf(1.1)
             * self.custom_accuracy().powf(8.0)
             * self.attrs.stars.powf(0.4)
             * 27.0;
@@ -366,13 +393,48 @@ impl TaikoPerformanceInner {
     }
 }
 
-fn accuracy(n300: u32, n100: u32, misses: u32) -> f64 {
-    if n300 + n100 + misses == 0 {
-        return 0.0;
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attributes() -> TaikoDifficultyAttributes {
+        TaikoDifficultyAttributes {
+            stars: 4.0,
+            hit_window: 35.0,
+            max_combo: 500,
+            ..Default::default()
+        }
     }
 
-    let numerator = 2 * n300 + n100;
-    let denominator = 2 * (n300 + n100 + misses);
+    #[test]
+    fn total_hits_of_passed_objects() {
+        let map = Beatmap::default();
+
+        let pp = TaikoPP::new(&map).attributes(attributes()).calculate();
+        assert_eq!(pp.total_hits(), 500);
 
-    f64::from(numerator) / f64::from(denominator)
+        let calc = TaikoPP::new(&map)
+            .attributes(attributes())
+            .passed_objects(100)
+            .misses(2);
+
+        let (state, _) = calc.clone().generate_state();
+        assert_eq!(state.total_hits(), 100);
+        assert_eq!(calc.calculate().total_hits(), 100);
+    }
+
+    #[test]
+    fn great_hit_window_override() {
+        let map = Beatmap::default();
+        let calc = TaikoPP::new(&map).attributes(attributes()).accuracy(97.0);
+
+        let default = calc.clone().calculate();
+        let same = calc.clone().great_hit_window(35.0).calculate();
+        let smaller = calc.great_hit_window(25.0).calculate();
+
+        assert_eq!(default, same);
+        assert!(smaller.pp_acc > default.pp_acc);
+        assert!(smaller.pp > default.pp);
+        assert_eq!(smaller.pp_difficulty, default.pp_difficulty);
+    }
 }
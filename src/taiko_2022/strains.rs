@@ -0,0 +1,20 @@
+/// The result of calculating the strains on an osu!taiko map.
+///
+/// Suitable to plot the difficulty of a map over time.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TaikoStrains {
+    /// Strain peaks of the color skill.
+    pub color: Vec<f64>,
+    /// Strain peaks of the rhythm skill.
+    pub rhythm: Vec<f64>,
+    /// Strain peaks of the stamina skill.
+    pub stamina: Vec<f64>,
+}
+
+impl TaikoStrains {
+    /// Time between two strains in ms.
+    ///
+    /// Note that the time is adjusted by the clock rate, e.g. with DT a
+    /// section covers 600ms of the original map.
+    pub const SECTION_LEN: f64 = 400.0;
+}
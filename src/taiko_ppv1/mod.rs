@@ -12,20 +12,41 @@ use crate::util::mods::Mods;
 
 const SECTION_LEN: f32 = 400.0;
 
-const STAR_SCALING_FACTOR: f32 = 0.04125;
+/// The ppv1 taiko star rating is the strain's difficulty value times this
+/// factor.
+pub const STAR_SCALING_FACTOR: f64 = 0.04125;
 
 /// Star calculation for osu!taiko maps.
-pub fn stars(map: &Beatmap, mods: u32) -> TaikoDifficultyAttributes {
-    let max_combo = map.hit_objects.iter().map(HitObject::is_circle).count() as u32;
+///
+/// In case of a partial play, e.g. a fail, one can specify the amount of passed objects.
+/// If no clock rate is specified, or it is `NaN` or infinite, it will be
+/// derived from the mods.
+pub fn stars(
+    map: &Beatmap,
+    mods: u32,
+    passed_objects: Option<usize>,
+    clock_rate: Option<f64>,
+) -> TaikoDifficultyAttributes {
+    let take = passed_objects.unwrap_or(map.hit_objects.len());
+
+    let max_combo = map
+        .hit_objects
+        .iter()
+        .take(take)
+        .map(HitObject::is_circle)
+        .count() as u32;
 
-    if map.hit_objects.len() < 2 {
+    if take < 2 || map.hit_objects.len() < 2 {
         return TaikoDifficultyAttributes {
             stars: 0.0,
             max_combo,
         };
     }
 
-    let clock_rate = mods.clock_rate() as f32;
+    let clock_rate = clock_rate
+        .filter(|rate| rate.is_finite())
+        .map_or_else(|| mods.clock_rate(), |rate| rate.clamp(0.01, 100.0))
+        as f32;
     let section_len = SECTION_LEN * clock_rate;
 
     // No strain for first object
@@ -36,6 +57,7 @@ pub fn stars(map: &Beatmap, mods: u32) -> TaikoDifficultyAttributes {
         .hit_objects
         .iter()
         .zip(map.hit_sounds.iter())
+        .take(take)
         .skip(1)
         .zip(map.hit_objects.iter().zip(map.hit_sounds.iter()))
         .map(|(base, prev)| DifficultyObject::new(base, prev, clock_rate));
@@ -65,11 +87,12 @@ pub fn stars(map: &Beatmap, mods: u32) -> TaikoDifficultyAttributes {
 
     strain.save_current_peak();
 
-    let stars = (strain.difficulty_value() * STAR_SCALING_FACTOR) as f64;
+    let stars = (strain.difficulty_value() * STAR_SCALING_FACTOR as f32) as f64;
 
     TaikoDifficultyAttributes { stars, max_combo }
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TaikoDifficultyAttributes {
     /// The final star rating.
     pub stars: f64,
@@ -77,6 +100,15 @@ pub struct TaikoDifficultyAttributes {
     pub max_combo: u32,
 }
 
+impl TaikoDifficultyAttributes {
+    /// Return the maximum combo.
+    #[inline]
+    pub fn max_combo(&self) -> u32 {
+        self.max_combo
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TaikoPerformanceAttributes {
     /// The difficulty attributes that were used for the performance calculation
     pub difficulty: TaikoDifficultyAttributes,
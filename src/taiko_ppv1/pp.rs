@@ -1,6 +1,7 @@
 use rosu_pp::{
     any::{DifficultyAttributes, PerformanceAttributes},
     model::hit_object::HitObject,
+    taiko::TaikoScoreState,
     Beatmap,
 };
 
@@ -8,6 +9,10 @@ use crate::util::{math::difficulty_range, mods::Mods};
 
 use super::{stars, TaikoDifficultyAttributes, TaikoPerformanceAttributes};
 
+/// Scale of the ppv1 taiko pp, applied to the combined strain and accuracy
+/// values.
+pub const PERFORMANCE_BASE_MULTIPLIER: f64 = 1.1;
+
 /// Calculator for pp on osu!taiko maps.
 ///
 /// # Example
@@ -45,6 +50,8 @@ pub struct TaikoPP<'m> {
     n_misses: u32,
     n300: Option<u32>,
     n100: Option<u32>,
+    passed_objects: Option<usize>,
+    clock_rate: Option<f64>,
 }
 
 impl<'m> TaikoPP<'m> {
@@ -59,6 +66,8 @@ impl<'m> TaikoPP<'m> {
             n_misses: 0,
             n300: None,
             n100: None,
+            passed_objects: None,
+            clock_rate: None,
         }
     }
 
@@ -128,16 +137,67 @@ impl<'m> TaikoPP<'m> {
         self
     }
 
+    /// Provide parameters through a [`TaikoScoreState`].
+    #[inline]
+    pub fn state(mut self, state: TaikoScoreState) -> Self {
+        let TaikoScoreState {
+            max_combo,
+            n300,
+            n100,
+            misses,
+        } = state;
+
+        self.combo = Some(max_combo);
+        self.n300 = Some(n300);
+        self.n100 = Some(n100);
+        self.n_misses = misses;
+
+        self
+    }
+
+    /// Amount of passed objects for partial plays, e.g. a fail.
+    #[inline]
+    pub fn passed_objects(mut self, passed_objects: usize) -> Self {
+        self.passed_objects.replace(passed_objects);
+
+        self
+    }
+
+    /// Adjust the clock rate used in the calculation.
+    ///
+    /// If none is specified, it will take the clock rate based on the mods
+    /// i.e. 1.5 for DT, 0.75 for HT and 1.0 otherwise.
+    ///
+    /// | Minimum | Maximum |
+    /// | :-----: | :-----: |
+    /// | 0.01    | 100     |
+    ///
+    /// A `NaN` or infinite value is ignored, i.e. the clock rate based on the
+    /// mods is used.
+    #[inline]
+    pub fn clock_rate(mut self, clock_rate: f64) -> Self {
+        self.clock_rate = if clock_rate.is_finite() {
+            Some(clock_rate.clamp(0.01, 100.0))
+        } else {
+            None
+        };
+
+        self
+    }
+
     /// Returns an object which contains the pp and stars.
     pub fn calculate(mut self) -> TaikoPerformanceAttributes {
-        let stars = self
-            .stars
-            .unwrap_or_else(|| stars(self.map, self.mods).stars as f32);
+        let stars = self.stars.unwrap_or_else(|| {
+            stars(self.map, self.mods, self.passed_objects, self.clock_rate).stars as f32
+        });
+
+        let take = self.passed_objects.unwrap_or(self.map.hit_objects.len());
 
         let n_hits = self
             .map
             .hit_objects
             .iter()
+            .take(take)
             .map(HitObject::is_circle)
             .count() as u32;
 
@@ -161,7 +221,7 @@ impl<'m> TaikoPP<'m> {
             self.acc = (2 * n300 + n100) as f32 / (2 * (n300 + n100 + misses)) as f32;
         }
 
-        let mut multiplier = 1.1;
+        let mut multiplier = PERFORMANCE_BASE_MULTIPLIER as f32;
 
         if self.mods.nf() {
             multiplier *= 0.9;
@@ -227,7 +287,8 @@ impl<'m> TaikoPP<'m> {
             od *= 0.5;
         }
 
-        let hit_window = difficulty_range_od(od as f64) / self.mods.clock_rate();
+        let clock_rate = self.clock_rate.unwrap_or_else(|| self.mods.clock_rate());
+        let hit_window = difficulty_range_od(od as f64) / clock_rate;
 
         (150.0 / hit_window as f32).powf(1.1)
             * self.acc.powi(15)
@@ -287,3 +348,118 @@ impl TaikoAttributeProvider for PerformanceAttributes {
         self.difficulty_attributes().attributes()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn map() -> Beatmap {
+        let hit_objects: String = (0..40)
+            .map(|i| {
+                let hit_sound = if i % 3 == 0 { 8 } else { 0 };
+
+                format!("256,192,{},1,{hit_sound},0:0:0:0:\n", 1000 + i * 150)
+            })
+            .collect();
+
+        let content = format!(
+            "osu file format v14
+
+[General]
+Mode: 1
+
+[Difficulty]
+HPDrainRate:5
+CircleSize:5
+OverallDifficulty:5
+ApproachRate:5
+SliderMultiplier:1.4
+SliderTickRate:1
+
+[TimingPoints]
+0,300,4,2,0,100,1,0
+
+[HitObjects]
+{hit_objects}"
+        );
+
+        Beatmap::from_bytes(content.as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn state_matches_setters() {
+        let map = map();
+
+        let state = TaikoScoreState {
+            max_combo: 25,
+            n300: 30,
+            n100: 8,
+            misses: 2,
+        };
+
+        let with_state = TaikoPP::new(&map).mods(8).state(state).calculate();
+
+        let with_setters = TaikoPP::new(&map)
+            .mods(8)
+            .combo(25)
+            .n300(30)
+            .n100(8)
+            .misses(2)
+            .calculate();
+
+        assert!(with_state.pp > 0.0);
+        assert_eq!(with_state.pp, with_setters.pp);
+    }
+
+    #[test]
+    fn passed_objects() {
+        let map = map();
+
+        let full = TaikoPP::new(&map).mods(8).calculate();
+
+        let all_passed = TaikoPP::new(&map)
+            .mods(8)
+            .passed_objects(map.hit_objects.len())
+            .calculate();
+
+        assert_eq!(full.pp, all_passed.pp);
+        assert_eq!(full.difficulty.stars, all_passed.difficulty.stars);
+
+        let partial = TaikoPP::new(&map).mods(8).passed_objects(20).calculate();
+
+        assert_eq!(partial.difficulty.max_combo, 20);
+        assert!(partial.difficulty.stars < full.difficulty.stars);
+        assert!(partial.pp < full.pp);
+    }
+
+    #[test]
+    fn clock_rate_matches_mods() {
+        let map = map();
+
+        let dt = TaikoPP::new(&map).mods(64).accuracy(97.0).calculate();
+
+        let rate = TaikoPP::new(&map)
+            .clock_rate(1.5)
+            .accuracy(97.0)
+            .calculate();
+
+        assert_eq!(dt.difficulty.stars, rate.difficulty.stars);
+        assert_eq!(dt.pp, rate.pp);
+
+        let nm = TaikoPP::new(&map).accuracy(97.0).calculate();
+        assert!(nm.pp < dt.pp);
+
+        for clock_rate in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            let ignored = TaikoPP::new(&map)
+                .clock_rate(clock_rate)
+                .accuracy(97.0)
+                .calculate();
+
+            assert_eq!(ignored.difficulty.stars, nm.difficulty.stars);
+            assert_eq!(ignored.pp, nm.pp);
+
+            let stars = stars(&map, 64, None, Some(clock_rate)).stars;
+            assert_eq!(stars, dt.difficulty.stars);
+        }
+    }
+}
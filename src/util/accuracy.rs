@@ -0,0 +1,134 @@
+//! Accuracy calculation from hitresults without any difficulty calculation.
+//!
+//! All functions return a value between `0.0` and `1.0`, or `0.0` if no
+//! hitresults were given.
+
+/// Accuracy of an osu!standard score.
+pub fn osu_accuracy(n300: u32, n100: u32, n50: u32, misses: u32) -> f64 {
+    if n300 + n100 + n50 + misses == 0 {
+        return 0.0;
+    }
+
+    let numerator = 6 * n300 + 2 * n100 + n50;
+    let denominator = 6 * (n300 + n100 + n50 + misses);
+
+    f64::from(numerator) / f64::from(denominator)
+}
+
+/// Accuracy of an osu!taiko score.
+pub fn taiko_accuracy(n300: u32, n100: u32, misses: u32) -> f64 {
+    if n300 + n100 + misses == 0 {
+        return 0.0;
+    }
+
+    let numerator = 2 * n300 + n100;
+    let denominator = 2 * (n300 + n100 + misses);
+
+    f64::from(numerator) / f64::from(denominator)
+}
+
+/// Accuracy of an osu!catch score.
+pub fn catch_accuracy(
+    n_fruits: u32,
+    n_droplets: u32,
+    n_tiny_droplets: u32,
+    n_tiny_droplet_misses: u32,
+    misses: u32,
+) -> f64 {
+    let numerator = n_fruits + n_droplets + n_tiny_droplets;
+    let denominator = numerator + n_tiny_droplet_misses + misses;
+
+    if denominator == 0 {
+        return 0.0;
+    }
+
+    f64::from(numerator) / f64::from(denominator)
+}
+
+/// Accuracy of an osu!mania score.
+///
+/// Perfect hits (`n320`) are weighted the same as 300s, i.e. the accuracy
+/// as displayed in osu!stable.
+pub fn mania_accuracy(n320: u32, n300: u32, n200: u32, n100: u32, n50: u32, misses: u32) -> f64 {
+    let total_hits = n320 + n300 + n200 + n100 + n50 + misses;
+
+    if total_hits == 0 {
+        return 0.0;
+    }
+
+    let numerator = 300 * (n320 + n300) + 200 * n200 + 100 * n100 + 50 * n50;
+    let denominator = 300 * total_hits;
+
+    f64::from(numerator) / f64::from(denominator)
+}
+
+/// Accuracy of an osu!mania score with osu!lazer's weighting.
+///
+/// Perfect hits (`n320`) are weighted slightly higher than 300s, i.e. the
+/// accuracy as displayed in osu!lazer.
+pub fn mania_lazer_accuracy(
+    n320: u32,
+    n300: u32,
+    n200: u32,
+    n100: u32,
+    n50: u32,
+    misses: u32,
+) -> f64 {
+    let total_hits = n320 + n300 + n200 + n100 + n50 + misses;
+
+    if total_hits == 0 {
+        return 0.0;
+    }
+
+    let numerator = 305 * n320 + 300 * n300 + 200 * n200 + 100 * n100 + 50 * n50;
+    let denominator = 305 * total_hits;
+
+    f64::from(numerator) / f64::from(denominator)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn osu() {
+        assert!((osu_accuracy(100, 0, 0, 0) - 1.0).abs() < f64::EPSILON);
+        assert!((osu_accuracy(97, 2, 1, 0) - 587.0 / 600.0).abs() < 1e-12);
+        assert!((osu_accuracy(0, 0, 0, 5)).abs() < f64::EPSILON);
+        assert!((osu_accuracy(0, 0, 0, 0)).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn taiko() {
+        assert!((taiko_accuracy(100, 0, 0) - 1.0).abs() < f64::EPSILON);
+        assert!((taiko_accuracy(90, 8, 2) - 0.94).abs() < 1e-12);
+        assert!((taiko_accuracy(0, 0, 0)).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn catch() {
+        assert!((catch_accuracy(10, 5, 85, 0, 0) - 1.0).abs() < f64::EPSILON);
+        assert!((catch_accuracy(40, 10, 45, 3, 2) - 0.95).abs() < 1e-12);
+        assert!((catch_accuracy(0, 0, 0, 0, 0)).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn mania() {
+        // 320s and 300s are weighted the same
+        assert!((mania_accuracy(50, 50, 0, 0, 0, 0) - 1.0).abs() < f64::EPSILON);
+        assert!((mania_accuracy(80, 10, 6, 2, 0, 2) - 284.0 / 300.0).abs() < 1e-12);
+        assert!((mania_accuracy(0, 0, 0, 0, 0, 0)).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn mania_lazer() {
+        assert!((mania_lazer_accuracy(100, 0, 0, 0, 0, 0) - 1.0).abs() < f64::EPSILON);
+        assert!((mania_lazer_accuracy(0, 0, 0, 0, 0, 0)).abs() < f64::EPSILON);
+
+        // 300s are worth less than 320s so the accuracies diverge
+        let lazer = mania_lazer_accuracy(80, 10, 6, 2, 0, 2);
+        let classic = mania_accuracy(80, 10, 6, 2, 0, 2);
+        assert!((lazer - 28_800.0 / 30_500.0).abs() < 1e-12);
+        assert!((lazer - classic).abs() > 1e-3);
+    }
+}
@@ -1,4 +1,9 @@
-use rosu_pp::model::control_point::{DifficultyPoint, TimingPoint};
+use rosu_pp::{
+    model::control_point::{DifficultyPoint, TimingPoint},
+    Beatmap,
+};
+
+use super::float_ext::FloatExt;
 
 pub fn timing_point_at(points: &[TimingPoint], time: f64) -> Option<&TimingPoint> {
     let i = points
@@ -14,3 +19,207 @@ pub fn difficulty_point_at(points: &[DifficultyPoint], time: f64) -> Option<&Dif
         .map_or_else(|i| i.checked_sub(1), Some)
         .map(|i| &points[i])
 }
+
+/// The most common BPM of a map, i.e. the BPM that osu! displays.
+///
+/// The BPM is multiplied by the given clock rate. See [`bpm_range`] for
+/// details on how the most common BPM is determined.
+pub fn bpm(map: &Beatmap, clock_rate: f64) -> f64 {
+    bpm_range(map, clock_rate).2
+}
+
+/// The minimum, maximum, and most common BPM of a map's timing points in
+/// that order.
+///
+/// The most common BPM is the one that lasts the longest until the last
+/// hit object; timing points after that object don't count towards it.
+///
+/// All values are multiplied by the given clock rate so pass `1.0` to get
+/// the BPM without rate changes.
+pub fn bpm_range(map: &Beatmap, clock_rate: f64) -> (f64, f64, f64) {
+    let points = &map.timing_points;
+
+    let Some(first) = points.first() else {
+        let bpm = 60_000.0 / TimingPoint::DEFAULT_BEAT_LEN * clock_rate;
+
+        return (bpm, bpm, bpm);
+    };
+
+    let last_time = map.hit_objects.last().map_or(first.time, |h| h.start_time);
+
+    let mut min_beat_len = f64::MAX;
+    let mut max_beat_len = f64::MIN;
+
+    // (beat length, total duration)
+    let mut durations: Vec<(f64, f64)> = Vec::with_capacity(points.len());
+
+    for (i, point) in points.iter().enumerate() {
+        min_beat_len = min_beat_len.min(point.beat_len);
+        max_beat_len = max_beat_len.max(point.beat_len);
+
+        if point.time > last_time {
+            continue;
+        }
+
+        // The first timing point counts from the start of the map
+        let start_time = if i == 0 { 0.0 } else { point.time };
+
+        let end_time = points
+            .get(i + 1)
+            .map_or(last_time, |next| next.time.min(last_time));
+
+        let duration = end_time - start_time;
+
+        match durations
+            .iter_mut()
+            .find(|(beat_len, _)| (*beat_len).eq(point.beat_len))
+        {
+            Some((_, total)) => *total += duration,
+            None => durations.push((point.beat_len, duration)),
+        }
+    }
+
+    let common_beat_len = durations
+        .iter()
+        .max_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map_or(first.beat_len, |(beat_len, _)| *beat_len);
+
+    let to_bpm = |beat_len: f64| 60_000.0 / beat_len * clock_rate;
+
+    (
+        to_bpm(max_beat_len),
+        to_bpm(min_beat_len),
+        to_bpm(common_beat_len),
+    )
+}
+
+/// The BPM changes of a map as `(start_time, bpm)` pairs in chronological
+/// order.
+///
+/// Consecutive timing points with the same BPM are collapsed into a single
+/// entry. The start times are divided and the BPMs multiplied by the given
+/// clock rate so pass `1.0` to get the values without rate changes.
+///
+/// Maps without timing points yield a single entry with osu!'s default BPM.
+pub fn bpm_changes(map: &Beatmap, clock_rate: f64) -> Vec<(f64, f64)> {
+    let to_bpm = |beat_len: f64| 60_000.0 / beat_len * clock_rate;
+
+    let Some(first) = map.timing_points.first() else {
+        return vec![(0.0, to_bpm(TimingPoint::DEFAULT_BEAT_LEN))];
+    };
+
+    let mut changes = vec![(first.time / clock_rate, to_bpm(first.beat_len))];
+    let mut prev_beat_len = first.beat_len;
+
+    for point in map.timing_points.iter().skip(1) {
+        if point.beat_len.eq(prev_beat_len) {
+            continue;
+        }
+
+        changes.push((point.time / clock_rate, to_bpm(point.beat_len)));
+        prev_beat_len = point.beat_len;
+    }
+
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use rosu_map::util::Pos;
+    use rosu_pp::model::hit_object::{HitObject, HitObjectKind};
+
+    use super::*;
+
+    fn map() -> Beatmap {
+        let timing_points = vec![
+            TimingPoint::new(0.0, 500.0),
+            TimingPoint::new(10_000.0, 250.0),
+            TimingPoint::new(12_000.0, 500.0),
+            // After the last hit object
+            TimingPoint::new(30_000.0, 400.0),
+        ];
+
+        let hit_objects = [0.0, 10_000.0, 20_000.0]
+            .iter()
+            .map(|&start_time| HitObject {
+                pos: Pos::default(),
+                start_time,
+                kind: HitObjectKind::Circle,
+            })
+            .collect();
+
+        Beatmap {
+            timing_points,
+            hit_objects,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn bpm_range_of_timing_points() {
+        let map = map();
+
+        assert_eq!(bpm_range(&map, 1.0), (120.0, 240.0, 120.0));
+        assert_eq!(bpm_range(&map, 1.5), (180.0, 360.0, 180.0));
+    }
+
+    #[test]
+    fn bpm_range_without_timing_points() {
+        let map = Beatmap::default();
+        let bpm = 60_000.0 / TimingPoint::DEFAULT_BEAT_LEN;
+
+        assert_eq!(bpm_range(&map, 1.0), (bpm, bpm, bpm));
+    }
+
+    #[test]
+    fn most_common_bpm() {
+        let map = map();
+
+        assert_eq!(bpm(&map, 1.0), 120.0);
+        assert_eq!(bpm(&map, 0.75), 90.0);
+    }
+
+    #[test]
+    fn most_common_bpm_counts_first_point_from_start() {
+        let mut map = map();
+
+        // The first point only lasts 1s but counts from the start of the map
+        map.timing_points[0].time = 9_000.0;
+        map.timing_points[2].time = 19_000.0;
+
+        assert_eq!(bpm(&map, 1.0), 120.0);
+    }
+
+    #[test]
+    fn bpm_changes_of_timing_points() {
+        let mut map = map();
+        map.timing_points
+            .insert(1, TimingPoint::new(5_000.0, 500.0));
+
+        let expected = vec![
+            (0.0, 120.0),
+            (10_000.0, 240.0),
+            (12_000.0, 120.0),
+            (30_000.0, 150.0),
+        ];
+
+        assert_eq!(bpm_changes(&map, 1.0), expected);
+
+        let expected = vec![
+            (0.0, 240.0),
+            (5_000.0, 480.0),
+            (6_000.0, 240.0),
+            (15_000.0, 300.0),
+        ];
+
+        assert_eq!(bpm_changes(&map, 2.0), expected);
+    }
+
+    #[test]
+    fn bpm_changes_without_timing_points() {
+        let map = Beatmap::default();
+        let bpm = 60_000.0 / TimingPoint::DEFAULT_BEAT_LEN;
+
+        assert_eq!(bpm_changes(&map, 1.0), vec![(0.0, bpm)]);
+    }
+}
@@ -0,0 +1,25 @@
+use std::borrow::Cow;
+
+use rosu_pp::{
+    model::{mode::GameMode, mods::GameMods},
+    Beatmap,
+};
+
+pub use rosu_pp::model::mode::ConvertError;
+
+/// Convert the map to the given mode, or borrow it if it already has that
+/// mode.
+///
+/// Maps that already have the given mode, e.g. because they were converted
+/// beforehand, are borrowed without any further work.
+pub fn convert_ref<'a>(
+    map: &'a Beatmap,
+    mode: GameMode,
+    mods: &GameMods,
+) -> Result<Cow<'a, Beatmap>, ConvertError> {
+    if map.mode == mode {
+        return Ok(Cow::Borrowed(map));
+    }
+
+    map.convert_ref(mode, mods)
+}
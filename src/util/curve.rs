@@ -0,0 +1,13 @@
+//! Slider curves, e.g. to sample positions along a slider's path.
+//!
+//! These are the same curves that are used throughout the difficulty
+//! calculations. Use [`Curve::new`] to create an owned curve or
+//! [`BorrowedCurve::new`] to reuse the allocations of [`CurveBuffers`]
+//! across multiple sliders. Both provide `position_at(progress)` for a
+//! progress between `0.0` and `1.0` as well as `dist()` for the curve's
+//! length, respecting the slider's expected length.
+
+pub use rosu_map::{
+    section::hit_objects::{BorrowedCurve, Curve, CurveBuffers, PathControlPoint, PathType},
+    util::Pos,
+};
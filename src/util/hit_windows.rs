@@ -0,0 +1,37 @@
+use rosu_pp::{model::mode::GameMode, Beatmap};
+
+/// Hit windows of a map after applying mods.
+///
+/// All values are in milliseconds and already adjusted by the clock rate.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct HitWindows {
+    /// Time between an object appearing and having to be hit, i.e. the
+    /// preempt time of the approach rate.
+    pub ar_ms: f64,
+    /// Hit window for the best judgement.
+    pub od_great: f64,
+    /// Hit window for the second best judgement.
+    ///
+    /// `None` for osu!catch which does not have such a judgement.
+    pub od_ok: Option<f64>,
+}
+
+/// Calculate the hit windows of a map after applying mods.
+///
+/// If the map's mode differs from the given mode, the map is treated as a
+/// convert.
+pub fn hit_windows(map: &Beatmap, mods: u32, mode: GameMode) -> HitWindows {
+    let is_convert = map.is_convert || map.mode != mode;
+
+    let hit_windows = map
+        .attributes()
+        .mode(mode, is_convert)
+        .mods(mods)
+        .hit_windows();
+
+    HitWindows {
+        ar_ms: hit_windows.ar,
+        od_great: hit_windows.od_great,
+        od_ok: hit_windows.od_ok,
+    }
+}
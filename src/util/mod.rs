@@ -1,6 +1,10 @@
+pub mod accuracy;
 pub mod control_points;
+pub mod convert;
+pub mod curve;
 pub mod difficulty_object;
 pub mod float_ext;
+pub mod hit_windows;
 pub mod limited_queue;
 pub mod math;
 pub mod mods;
@@ -1,3 +1,10 @@
+use std::{
+    error::Error,
+    fmt::{Display, Formatter, Result as FmtResult},
+};
+
+use rosu_pp::model::mods::GameMods;
+
 pub trait Mods: Copy {
     fn nf(self) -> bool;
     fn ez(self) -> bool;
@@ -41,3 +48,182 @@ impl Mods for u32 {
     impl_mods_fn!(fl, 1 << 10);
     impl_mods_fn!(so, 1 << 12);
 }
+
+/// Convert [`GameMods`] into their legacy bit values.
+///
+/// Lazer mods without a legacy counterpart are dropped and custom settings
+/// such as a custom speed change are not considered. Just like for
+/// [`parse_mods`], NC implies DT and PF implies SD.
+pub fn legacy_bits(mods: &GameMods) -> u32 {
+    let bits = match mods {
+        GameMods::Lazer(mods) => mods.bits(),
+        GameMods::Intermode(mods) => mods.bits(),
+        GameMods::Legacy(mods) => mods.bits(),
+    };
+
+    with_implied_bits(bits)
+}
+
+fn with_implied_bits(mut bits: u32) -> u32 {
+    // NC implies DT
+    if bits & (1 << 9) != 0 {
+        bits |= 1 << 6;
+    }
+
+    // PF implies SD
+    if bits & (1 << 14) != 0 {
+        bits |= 1 << 5;
+    }
+
+    bits
+}
+
+/// The clock rate of lazer [`GameMods`], including custom speed changes.
+///
+/// Returns `None` for legacy and intermode mods, as well as for lazer mods
+/// without a single clock rate, in which case the clock rate follows from
+/// the legacy bits.
+pub fn lazer_clock_rate(mods: &GameMods) -> Option<f64> {
+    match mods {
+        GameMods::Lazer(mods) => mods.clock_rate(),
+        GameMods::Intermode(_) | GameMods::Legacy(_) => None,
+    }
+}
+
+/// Parse a string of mod acronyms into their bit values.
+///
+/// Acronyms are case-insensitive and may either be concatenated (`"HDDT"`)
+/// or separated by commas, plus signs, or whitespace (`"HD,DT"`, `"+HD DT"`).
+/// `"NM"` denotes no mods.
+///
+/// Mods that only exist in lazer and thus have no legacy bit value, such as
+/// `CL`, `BL`, or `TC`, are accepted but don't set any bits.
+///
+/// See <https://github.com/ppy/osu-api/wiki#mods>
+pub fn parse_mods(s: &str) -> Result<u32, ParseModsError> {
+    let mut mods = 0;
+
+    for token in s.split(|c: char| c == ',' || c == '+' || c.is_whitespace()) {
+        let token = token.as_bytes();
+
+        if token.len() % 2 != 0 {
+            return Err(ParseModsError::new(token));
+        }
+
+        for acronym in token.chunks_exact(2) {
+            mods |= acronym_bits(acronym).ok_or_else(|| ParseModsError::new(acronym))?;
+        }
+    }
+
+    Ok(mods)
+}
+
+fn acronym_bits(acronym: &[u8]) -> Option<u32> {
+    let bits = match [
+        acronym[0].to_ascii_uppercase(),
+        acronym[1].to_ascii_uppercase(),
+    ] {
+        [b'N', b'M'] => 0,
+        [b'N', b'F'] => 1 << 0,
+        [b'E', b'Z'] => 1 << 1,
+        [b'T', b'D'] => 1 << 2,
+        [b'H', b'D'] => 1 << 3,
+        [b'H', b'R'] => 1 << 4,
+        [b'S', b'D'] => 1 << 5,
+        [b'D', b'T'] => 1 << 6,
+        [b'R', b'X'] => 1 << 7,
+        [b'H', b'T'] => 1 << 8,
+        // NC implies DT
+        [b'N', b'C'] => (1 << 9) | (1 << 6),
+        [b'F', b'L'] => 1 << 10,
+        [b'A', b'T'] => 1 << 11,
+        [b'S', b'O'] => 1 << 12,
+        [b'A', b'P'] => 1 << 13,
+        // PF implies SD
+        [b'P', b'F'] => (1 << 14) | (1 << 5),
+        [b'4', b'K'] => 1 << 15,
+        [b'5', b'K'] => 1 << 16,
+        [b'6', b'K'] => 1 << 17,
+        [b'7', b'K'] => 1 << 18,
+        [b'8', b'K'] => 1 << 19,
+        [b'F', b'I'] => 1 << 20,
+        [b'R', b'D'] => 1 << 21,
+        [b'C', b'N'] => 1 << 22,
+        [b'T', b'P'] => 1 << 23,
+        [b'9', b'K'] => 1 << 24,
+        [b'1', b'K'] => 1 << 26,
+        [b'3', b'K'] => 1 << 27,
+        [b'2', b'K'] => 1 << 28,
+        [b'V', b'2'] => 1 << 29,
+        [b'M', b'R'] => 1 << 30,
+        // Lazer-only mods without legacy bits
+        [b'C', b'L'] | [b'B', b'L'] | [b'T', b'C'] | [b'D', b'A'] => 0,
+        _ => return None,
+    };
+
+    Some(bits)
+}
+
+/// Error when parsing mod acronyms through [`parse_mods`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseModsError {
+    acronym: String,
+}
+
+impl ParseModsError {
+    fn new(acronym: &[u8]) -> Self {
+        Self {
+            acronym: String::from_utf8_lossy(acronym).into_owned(),
+        }
+    }
+
+    /// The acronym that could not be parsed.
+    pub fn acronym(&self) -> &str {
+        &self.acronym
+    }
+}
+
+impl Display for ParseModsError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "unknown mod acronym `{}`", self.acronym)
+    }
+}
+
+impl Error for ParseModsError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_concatenated_and_separated() {
+        assert_eq!(parse_mods("HDDT"), Ok(8 + 64));
+        assert_eq!(parse_mods("hd dt"), Ok(8 + 64));
+        assert_eq!(parse_mods("+HD,dt"), Ok(8 + 64));
+        assert_eq!(parse_mods("NM"), Ok(0));
+        assert_eq!(parse_mods(""), Ok(0));
+    }
+
+    #[test]
+    fn parse_implied_mods() {
+        assert_eq!(parse_mods("NC"), Ok((1 << 9) | (1 << 6)));
+        assert_eq!(parse_mods("PF"), Ok((1 << 14) | (1 << 5)));
+        assert!(parse_mods("NC").unwrap().dt());
+    }
+
+    #[test]
+    fn legacy_bits_implied_mods() {
+        assert_eq!(legacy_bits(&GameMods::from(1 << 9)), (1 << 9) | (1 << 6));
+        assert_eq!(legacy_bits(&GameMods::from(1 << 14)), (1 << 14) | (1 << 5));
+        assert_eq!(legacy_bits(&GameMods::from(8 + 64)), 8 + 64);
+    }
+
+    #[test]
+    fn parse_invalid() {
+        let err = parse_mods("HDD").unwrap_err();
+        assert_eq!(err.acronym(), "HDD");
+
+        let err = parse_mods("HDXX").unwrap_err();
+        assert_eq!(err.acronym(), "XX");
+    }
+}
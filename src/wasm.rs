@@ -0,0 +1,85 @@
+//! Bindings for JavaScript through [`wasm_bindgen`].
+//!
+//! Every function takes the content of a `.osu` file as bytes, the mods as
+//! bit values, and an accuracy between `0.0` and `100.0`. The resulting
+//! performance attributes are returned as JSON string.
+//!
+//! The JavaScript package is generated with
+//! `wasm-pack build --target web -- --features wasm`.
+//!
+//! ```js
+//! import init, { osu2022Pp } from "rosu-pp-older";
+//!
+//! await init();
+//!
+//! const bytes = new Uint8Array(await file.arrayBuffer());
+//! const attrs = JSON.parse(osu2022Pp(bytes, 8 + 64, 98.5));
+//!
+//! console.log(`PP: ${attrs.pp} | Stars: ${attrs.difficulty.stars}`);
+//! ```
+
+use rosu_pp::Beatmap;
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+use crate::{fruits_2022, mania_2022, osu_2022, taiko_2022};
+
+/// Calculate the performance attributes of osu!standard's 2022 version.
+#[wasm_bindgen(js_name = osu2022Pp)]
+pub fn osu_2022_pp(map: &[u8], mods: u32, acc: f64) -> Result<String, JsError> {
+    let map = parse_map(map)?;
+
+    let attrs = osu_2022::OsuPP::new(&map)
+        .mods(mods)
+        .accuracy(acc)
+        .calculate();
+
+    to_json(&attrs)
+}
+
+/// Calculate the performance attributes of osu!taiko's 2022 version.
+#[wasm_bindgen(js_name = taiko2022Pp)]
+pub fn taiko_2022_pp(map: &[u8], mods: u32, acc: f64) -> Result<String, JsError> {
+    let map = parse_map(map)?;
+
+    let attrs = taiko_2022::TaikoPP::new(&map)
+        .mods(mods)
+        .accuracy(acc)
+        .calculate();
+
+    to_json(&attrs)
+}
+
+/// Calculate the performance attributes of osu!catch's 2022 version.
+#[wasm_bindgen(js_name = fruits2022Pp)]
+pub fn fruits_2022_pp(map: &[u8], mods: u32, acc: f64) -> Result<String, JsError> {
+    let map = parse_map(map)?;
+
+    let attrs = fruits_2022::FruitsPP::new(&map)
+        .mods(mods)
+        .accuracy(acc)
+        .calculate();
+
+    to_json(&attrs)
+}
+
+/// Calculate the performance attributes of osu!mania's 2022 version.
+#[wasm_bindgen(js_name = mania2022Pp)]
+pub fn mania_2022_pp(map: &[u8], mods: u32, acc: f64) -> Result<String, JsError> {
+    let map = parse_map(map)?;
+
+    let attrs = mania_2022::ManiaPP::new(&map)
+        .mods(mods)
+        .accuracy(acc)
+        .calculate();
+
+    to_json(&attrs)
+}
+
+fn parse_map(bytes: &[u8]) -> Result<Beatmap, JsError> {
+    Beatmap::from_bytes(bytes).map_err(|err| JsError::new(&format!("invalid beatmap: {err}")))
+}
+
+fn to_json(attrs: &impl Serialize) -> Result<String, JsError> {
+    serde_json::to_string(attrs).map_err(|err| JsError::new(&err.to_string()))
+}